@@ -0,0 +1,45 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use primes::bigint::{mul_karatsuba, mul_schoolbook, sqr_karatsuba, sqr_schoolbook};
+use primes::rng;
+
+
+const ROUNDS: u32 = 2000;
+const SAMPLES: usize = 15;
+
+
+// nanoseconds per call, the fastest of several samples to filter out noise
+// from other processes
+fn time<F: FnMut()>(mut f: F) -> f64 {
+    let mut best = f64::MAX;
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+        for _ in 0..ROUNDS { f(); }
+        best = best.min(start.elapsed().as_nanos() as f64 / ROUNDS as f64);
+    }
+    best
+}
+
+
+// schoolbook against karatsuba with recursion down to each threshold, on
+// every operand length a BigInt product can have (barrett uses up to 17
+// chunks). mul_limbs stays with schoolbook while no column wins throughout
+fn main() {
+    let thresholds = [4, 6, 8, 12, 16, 24];
+    println!("{:>6} {:>12} {}", "chunks", "schoolbook", thresholds.map(|t| format!("{:>9}", t)).join(""));
+
+    for len in [4, 6, 8, 10, 12, 14, 16, 17, 20, 24, 28, 32] {
+        let a: Vec<u64> = (0..len).map(|_| rng::u64()).collect();
+        let b: Vec<u64> = (0..len).map(|_| rng::u64()).collect();
+        let mut out = vec![0; 2 * len];
+
+        let school = time(|| mul_schoolbook(black_box(&a), black_box(&b), &mut out));
+        let karatsuba = thresholds.map(|t| time(|| mul_karatsuba(black_box(&a), black_box(&b), &mut out, t)));
+        println!("mul {:>2} {:>10.0}ns {}", len, school, karatsuba.map(|ns| format!("{:>7.0}ns", ns)).join(""));
+
+        let school = time(|| sqr_schoolbook(black_box(&a), &mut out));
+        let karatsuba = thresholds.map(|t| time(|| sqr_karatsuba(black_box(&a), &mut out, t)));
+        println!("sqr {:>2} {:>10.0}ns {}", len, school, karatsuba.map(|ns| format!("{:>7.0}ns", ns)).join(""));
+    }
+}
@@ -1,27 +1,62 @@
 use crate::rng;
 use crate::utils;
 use crate::BigInt;
+use crate::bigint::{N, limbs_shr, sqr_limbs};
+use crate::barrett::BarrettCtx;
+use crate::certificate::{Certificate, FactorProof};
+use crate::sieve::sieving_primes;
+use crate::primality::{Bpsw, Fermat, MillerRabin, PrimalityTest};
+use crate::special::SpecialCtx;
 
 
 
+#[allow(clippy::large_enum_variant)]
 #[derive(PartialEq, Debug)]
 pub enum PrimeResult {
     Prime,
-    Composite,
+    Composite(Evidence),
     Unknown,
     ProbablePrime,
 }
 
 
+impl PrimeResult {
+    pub fn is_composite(&self) -> bool {
+        matches!(self, PrimeResult::Composite(_))
+    }
+}
+
+
+// why a number was rejected
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Evidence {
+    // 0 and 1, which have no factor to show
+    BelowTwo,
+    // a divisor d with 1 < d < n
+    Factor(BigInt),
+    // a base a with a^(n-1) != 1
+    FermatWitness(BigInt),
+    // a base a with a^((n-1)/2) != (a / n)
+    EulerWitness(BigInt),
+    // x with x^2 = 1 but x != 1, -1
+    SquareRoot(BigInt),
+    // a lucas sequence or the frobenius condition failed, there is no short
+    // witness
+    Sequence,
+}
+
+
+impl Evidence {
 
-pub fn trial_division_simple(n: u16) -> PrimeResult {
-    let root_n = (n as f64).sqrt() as u16;
-    for x in 3..root_n {
-        if n % x == 0 {
-            return PrimeResult::Composite;
+    // a nontrivial factor of n where the evidence contains one
+    pub fn factor(&self, n: BigInt) -> Option<BigInt> {
+        match *self {
+            Evidence::Factor(d) => Some(d),
+            Evidence::SquareRoot(x) => Some(utils::gcd(x.decrease(), n)),
+            _ => None,
         }
     }
-    PrimeResult::Prime
+
 }
 
 
@@ -29,14 +64,15 @@ pub fn trial_division_simple(n: u16) -> PrimeResult {
 pub fn trial_division(n: u64, start: u64) -> PrimeResult {
     // assumption: n is odd, n > 3 and start > 3
 
-    if n % 3 == 0 {
-        return PrimeResult::Composite;
+    if n.is_multiple_of(3) {
+        return PrimeResult::Composite(Evidence::Factor(BigInt::from(3)));
     }
 
-    let root_n = (n as f64).sqrt() as u64;
+    let root_n = utils::isqrt_u64(n);
     for x in (start..(root_n + 1)).step_by(6) {
-        if n % x == 0 || n % (x + 2) == 0 {
-            return PrimeResult::Composite;
+        if n.is_multiple_of(x) || n.is_multiple_of(x + 2) {
+            let factor = if n.is_multiple_of(x) { x } else { x + 2 };
+            return PrimeResult::Composite(Evidence::Factor(BigInt::from(factor as u128)));
         }
     }
     PrimeResult::Prime
@@ -45,10 +81,42 @@ pub fn trial_division(n: u64, start: u64) -> PrimeResult {
 
 
 pub fn fermat_test_u128(num: u128, k: usize) -> PrimeResult {
+    Fermat::new(k).test(num)
+}
+
+
+
+pub fn fermat_test(n: BigInt, k: usize) -> PrimeResult {
+    Fermat::new(k).test(n)
+}
+
+
+
+pub fn miller_rabin_test_u128(n: u128, k: usize) -> PrimeResult {
+    MillerRabin::new(k).test(n)
+}
+
+
+
+// assumption: n has at most 1024 bits
+pub fn miller_rabin_test(n: BigInt, k: usize) -> PrimeResult {
+    MillerRabin::new(k).test(n)
+}
+
+
+
+pub fn solovay_strassen_test_u128(n: u128, k: usize) -> PrimeResult {
+    // assumption: n is odd and n > 3
+
+    // mod_exp multiplies with mul_mod_u128, so n may use all 128 bits
     for _ in 0..k {
-        let base = rng::u128_range(2, num - 1);
-        if utils::mod_exp(base, num - 1, num) != 1 {
-            return PrimeResult::Composite;
+        let base = rng::u128() % (n - 3) + 2;
+        let euler = utils::mod_exp(base, (n - 1) / 2, n);
+        match utils::jacobi_u128(base, n) {
+            0 => return PrimeResult::Composite(Evidence::Factor(BigInt::from(utils::gcd_u128(base, n)))),
+            1 if euler == 1 => continue,
+            -1 if euler == n - 1 => continue,
+            _ => return PrimeResult::Composite(Evidence::EulerWitness(BigInt::from(base))),
         }
     }
     PrimeResult::ProbablePrime
@@ -56,75 +124,675 @@ pub fn fermat_test_u128(num: u128, k: usize) -> PrimeResult {
 
 
 
-pub fn miller_rabin_test_u128(n: u128, k: usize) -> PrimeResult {
+pub fn solovay_strassen_test(n: BigInt, k: usize) -> PrimeResult {
+
+    // assumption: n is odd and n > 3
+
+    let one = BigInt::from(1);
+    let n_minus_1 = n.decrease();
+    let n_minus_3 = n_minus_1.decrease().decrease();
+    let half = n_minus_1 >> 1;
+
+    let ctx = BarrettCtx::new(n);
+    let mut bytes = [0; (1024 / 16)];
 
-    let mut s = 0;
-    let mut d = n - 1;
-    while d % 2 == 0 {
-        d = d / 2;
-        s += 1;
+    for _ in 0..k {
+        rng::insert_random_bytes(&mut bytes).unwrap();
+        let base = (BigInt::from(bytes.as_slice()) % n_minus_3).increase_by_2();
+
+        // a^((n-1)/2) has to match the jacobi symbol (a / n)
+        let euler = ctx.pow_mod(base, half);
+        match utils::jacobi(base, n) {
+            0 => return PrimeResult::Composite(Evidence::Factor(utils::gcd(base, n))),
+            1 if euler == one => continue,
+            -1 if euler == n_minus_1 => continue,
+            _ => return PrimeResult::Composite(Evidence::EulerWitness(base)),
+        }
     }
 
-    'main_loop: for _ in 0..k {
-        let base = rng::u128_range(2, n - 2);
+    PrimeResult::ProbablePrime
+}
+
 
-        let mut x = utils::mod_exp(base, d, n);
-        if x == 1 || x == n - 1 { continue 'main_loop; }
 
-        for _ in 0..(s - 1) {
-            x = utils::mod_exp(x, 2, n);
-            if x == n - 1 { continue 'main_loop; }
+// arithmetic in Z/nZ[x] / (x^2 - bx - c), u + vx is stored as (u, v)
+struct QuadraticRing {
+    ctx: BarrettCtx,
+    b: BigInt,
+    c: BigInt,
+}
+
+
+impl QuadraticRing {
+
+    fn add(&self, a: BigInt, b: BigInt) -> BigInt {
+        let sum = a + b;
+        if sum >= self.ctx.modulus { sum - self.ctx.modulus } else { sum }
+    }
+
+    // x^2 = bx + c
+    fn mul(&self, p: (BigInt, BigInt), q: (BigInt, BigInt)) -> (BigInt, BigInt) {
+        let ctx = &self.ctx;
+        let vv = ctx.mul_mod(p.1, q.1);
+        let u = self.add(ctx.mul_mod(p.0, q.0), ctx.mul_mod(vv, self.c));
+        let v = self.add(self.add(ctx.mul_mod(p.0, q.1), ctx.mul_mod(p.1, q.0)), ctx.mul_mod(vv, self.b));
+        (u, v)
+    }
+
+    fn pow(&self, base: (BigInt, BigInt), exponent: BigInt) -> (BigInt, BigInt) {
+        let mut result = (BigInt::from(1), BigInt::from(0));
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(result, result);
+            if exponent.bit(i) {
+                result = self.mul(result, base);
+            }
         }
+        result
+    }
+
+}
+
 
-        return PrimeResult::Composite;
+
+// grantham's quadratic frobenius test, each round picks b and c with
+// (b^2 + 4c / n) = -1 and (-c / n) = 1. small n are decided by the trial
+// division
+pub fn frobenius_test(n: BigInt, k: usize) -> PrimeResult {
+
+    // assumption: n is odd and n > 3
+
+    for p in sieving_primes(64 * N) {
+        if BigInt::from((p * p) as u128) > n {
+            return PrimeResult::Prime;
+        }
+        if n.rem_u64(p) == 0 {
+            return PrimeResult::Composite(Evidence::Factor(BigInt::from(p as u128)));
+        }
+    }
+    if utils::is_perfect_square(n) {
+        return PrimeResult::Composite(Evidence::Factor(utils::isqrt(n)));
+    }
+
+    let one = BigInt::from(1);
+    let n_minus_1 = n.decrease();
+    let n_minus_3 = n_minus_1.decrease().decrease();
+    let ctx = BarrettCtx::new(n);
+    let mut bytes = [0; (1024 / 16)];
+
+    // n^2 - 1 = 2^r s is split into n - 1 and n + 1 to stay below 2n bits
+    let r_minus = n_minus_1.trailing_zeros();
+    let r_plus = n.increase().trailing_zeros();
+    let s_minus = n_minus_1 >> r_minus;
+    let s_plus = n.increase() >> r_plus;
+
+    for _ in 0..k {
+        let mut random = || {
+            rng::insert_random_bytes(&mut bytes).unwrap();
+            (BigInt::from(bytes.as_slice()) % n_minus_3).increase_by_2()
+        };
+        let (b, c) = loop {
+            let b = random();
+            let c = random();
+            let disc = ctx.reduce(ctx.mul_mod(b, b) + ctx.mul_mod(c, BigInt::from(4)));
+            match (utils::jacobi(n - c, n), utils::jacobi(disc, n)) {
+                (1, -1) => break (b, c),
+                (0, _) => return PrimeResult::Composite(Evidence::Factor(utils::gcd(c, n))),
+                // disc = 0 mod n says nothing about n, so b and c are drawn again
+                (_, 0) if !disc.is_zero() => {
+                    return PrimeResult::Composite(Evidence::Factor(utils::gcd(disc, n)));
+                }
+                _ => {},
+            }
+        };
+        let ring = QuadraticRing { ctx, b, c };
+        let x = (BigInt::from(0), one);
+
+        // x^((n+1)/2) has to lie in Z/nZ and square to -c
+        let half = ring.pow(x, n.increase() >> 1);
+        if !half.1.is_zero() || ctx.square_mod(half.0) != n - c {
+            return PrimeResult::Composite(Evidence::Sequence);
+        }
+
+        // x^s = 1 or x^(2^j s) = -1 for some j <= r - 2
+        let mut y = ring.pow(ring.pow(x, s_minus), s_plus);
+        if y == (one, BigInt::from(0)) {
+            continue;
+        }
+        let mut passed = false;
+        for _ in 0..(r_minus + r_plus - 1) {
+            if y == (n_minus_1, BigInt::from(0)) {
+                passed = true;
+                break;
+            }
+            y = ring.mul(y, y);
+        }
+        if !passed {
+            return PrimeResult::Composite(Evidence::Sequence);
+        }
     }
 
     PrimeResult::ProbablePrime
 }
 
 
-pub fn miller_rabin_test(n: BigInt, k: usize) -> PrimeResult {
 
-    let zero = BigInt::zero();
+// runs the probabilistic tests on the same n and returns every verdict if
+// they disagree, Prime and ProbablePrime count as the same answer
+pub fn find_disagreement(n: BigInt, k: usize) -> Option<Vec<(&'static str, PrimeResult)>> {
+    if n.is_even() || n <= BigInt::from(3) || n.bits() > 1024 {
+        panic!("Number must be odd, greater than 3 and at most 1024 bits");
+    }
+
+    let mut results = vec![
+        ("miller-rabin", miller_rabin_test(n, k)),
+        ("solovay-strassen", solovay_strassen_test(n, k)),
+        ("frobenius", frobenius_test(n, k)),
+        ("baillie-psw", Bpsw.test(n)),
+    ];
+    if n.bits() <= 128 {
+        let n_u128 = n.chunks[0] as u128 | (n.chunks[1] as u128) << 64;
+        results.push(("solovay-strassen u128", solovay_strassen_test_u128(n_u128, k)));
+    }
+
+    let composite = results.iter().filter(|(_, result)| result.is_composite()).count();
+    if composite == 0 || composite == results.len() { None } else { Some(results) }
+}
+
+
+
+// exponents p of the mersenne primes 2^p - 1 that fit in a BigInt
+pub const MERSENNE_PRIME_EXPONENTS: [usize; 15] = [
+    2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607, 1279,
+];
+
+
+// x mod 2^p - 1 for x < 2^(2p), since 2^p = 1 the bits above p are added
+// onto the low p bits
+fn reduce_mersenne(wide: &[u64; 2 * N], p: usize, mersenne: BigInt) -> BigInt {
+    let mut low = limbs_shr(wide, 0);
+    low -= (low >> p) << p;
+    let high = limbs_shr(wide, p);
+
+    let mut sum = low + high;
+    while sum >= mersenne {
+        sum -= mersenne;
+    }
+    sum
+}
+
+
+// lucas-lehmer test for 2^p - 1 with p < 64 * N, the result is always
+// Prime or Composite
+pub fn lucas_lehmer(p: usize) -> PrimeResult {
+    if !(2..(64 * N)).contains(&p) {
+        panic!("Mersenne exponent must be between 2 and {}", 64 * N - 1);
+    }
+    if p == 2 {
+        return PrimeResult::Prime;
+    }
+    // 2^p - 1 is divisible by 2^d - 1 for every divisor d of p
+    if !crate::small_is_prime(p as u64) {
+        let d = (2..p).find(|d| p.is_multiple_of(*d)).unwrap();
+        return PrimeResult::Composite(Evidence::Factor((BigInt::from(1) << d) - BigInt::from(1)));
+    }
+
+    let two = BigInt::from(2);
+    let mersenne = (BigInt::from(1) << p) - BigInt::from(1);
+    let limbs = p.div_ceil(64);
+    let mut wide = [0; 2 * N];
+
+    // s = s^2 - 2 starting from 4, p - 2 times
+    let mut s = BigInt::from(4);
+    for _ in 0..(p - 2) {
+        sqr_limbs(&s.chunks[..limbs], &mut wide);
+        s = reduce_mersenne(&wide, p, mersenne);
+        s = if s >= two { s - two } else { s + mersenne - two };
+    }
+
+    if s.is_zero() { PrimeResult::Prime } else { PrimeResult::Composite(Evidence::Sequence) }
+}
+
+
+
+// proth's theorem: k * 2^n + 1 with k < 2^n is prime if and only if
+// a^((N-1)/2) = -1 for a quadratic non-residue a. always Prime or Composite
+pub fn proth_test(k: u64, n: usize) -> PrimeResult {
+    if k == 0 || (n < 64 && k >> n != 0) {
+        panic!("Proth numbers need 0 < k < 2^n");
+    }
+    let ctx = SpecialCtx::proth(k, n);
+    let modulus = ctx.modulus;
+    let n_minus_1 = modulus - BigInt::from(1);
+
+    // squares have no non-residue, the other numbers have a small one
+    if utils::is_perfect_square(modulus) {
+        return PrimeResult::Composite(Evidence::Factor(utils::isqrt(modulus)));
+    }
+    let mut a = BigInt::from(2);
+    loop {
+        match utils::jacobi(a, modulus) {
+            -1 => break,
+            0 if a == modulus => return PrimeResult::Prime,
+            0 => return PrimeResult::Composite(Evidence::Factor(utils::gcd(a, modulus))),
+            _ => a = a.increase(),
+        }
+    }
+
+    // (a / N) = -1, so a is an euler witness when the test fails
+    if ctx.pow_mod(a, n_minus_1 >> 1) == n_minus_1 {
+        PrimeResult::Prime
+    } else {
+        PrimeResult::Composite(Evidence::EulerWitness(a))
+    }
+}
+
+
+// pepin's test: the fermat number 2^(2^m) + 1 is prime if and only if
+// 3^((F-1)/2) = -1
+pub fn pepin_test(m: u32) -> PrimeResult {
+    // F11 has 2049 bits, more than SpecialCtx can reduce
+    if m > 10 {
+        panic!("Fermat number index must be at most 10");
+    }
+    if m == 0 {
+        return PrimeResult::Prime;
+    }
+    let ctx = SpecialCtx::proth(1, 1 << m);
+    let n_minus_1 = ctx.modulus - BigInt::from(1);
+
+    // (F-1)/2 = 2^(2^m - 1)
+    let mut x = BigInt::from(3);
+    for _ in 0..((1 << m) - 1) {
+        x = ctx.square_mod(x);
+    }
+    // (3 / F) = -1 for m > 0
+    if x == n_minus_1 { PrimeResult::Prime } else { PrimeResult::Composite(Evidence::EulerWitness(BigInt::from(3))) }
+}
+
+
+
+// riesel primes k * 2^n - 1 that fit in a BigInt, as (k, n) with k < 2^n
+pub const RIESEL_PRIMES: [(u64, usize); 77] = [
+    (3, 2), (3, 3), (3, 4), (3, 6), (3, 7), (3, 11), (3, 18), (3, 34), (3, 38), (3, 43),
+    (3, 55), (3, 64), (3, 76), (3, 94), (3, 103), (3, 143), (3, 206), (3, 216),
+    (3, 306), (3, 324), (3, 391), (3, 458), (3, 470), (3, 827), (3, 1274),
+    (5, 4), (5, 8), (5, 10), (5, 12), (5, 14), (5, 18), (5, 32), (5, 48), (5, 54),
+    (5, 72), (5, 148), (5, 184), (5, 248), (5, 270), (5, 274), (5, 420), (5, 1340),
+    (5, 1438), (5, 1522), (5, 1638), (5, 1754), (5, 1884),
+    (7, 5), (7, 9), (7, 17), (7, 21), (7, 29), (7, 45), (7, 177),
+    (15, 4), (15, 5), (15, 10), (15, 14), (15, 17), (15, 31), (15, 41), (15, 73),
+    (15, 80), (15, 82), (15, 116), (15, 125), (15, 145), (15, 157), (15, 172),
+    (15, 202), (15, 224), (15, 266), (15, 289), (15, 293), (15, 463), (15, 1004),
+    (15, 1246),
+];
+
+
+// x - c mod m for x < m and small c
+fn sub_small(x: BigInt, c: BigInt, modulus: BigInt) -> BigInt {
+    if x >= c { x - c } else { x + modulus - c }
+}
+
+
+// lucas-lehmer-riesel test for k * 2^n - 1 with k odd and k < 2^n, the
+// result is always Prime or Composite. the sequence starts at V_k(P, 1) with
+// P chosen as in rödseth, (P - 2 / N) = 1 and (P + 2 / N) = -1
+pub fn llr_test(k: u64, n: usize) -> PrimeResult {
+    if k.is_multiple_of(2) || n < 2 || (n < 64 && k >> n != 0) {
+        panic!("Riesel numbers need an odd k < 2^n and n >= 2");
+    }
+    let ctx = SpecialCtx::riesel(k, n);
+    let modulus = ctx.modulus;
+    let two = BigInt::from(2);
+
+    if utils::is_perfect_square(modulus) {
+        return PrimeResult::Composite(Evidence::Factor(utils::isqrt(modulus)));
+    }
+    let mut p = 3u128;
+    loop {
+        let below = utils::jacobi(BigInt::from(p - 2), modulus);
+        let above = utils::jacobi(BigInt::from(p + 2), modulus);
+        // a common factor with a small number
+        if below == 0 || above == 0 {
+            let factor = BigInt::from(if below == 0 { p - 2 } else { p + 2 });
+            if factor == modulus {
+                return PrimeResult::Prime;
+            }
+            return PrimeResult::Composite(Evidence::Factor(utils::gcd(factor, modulus)));
+        }
+        if below == 1 && above == -1 {
+            break;
+        }
+        p += 1;
+    }
+
+    // V_k by the ladder (V_m, V_m+1), V_2m = V_m^2 - 2 and
+    // V_2m+1 = V_m V_m+1 - P
+    // P only exceeds N for the smallest N
+    let big_p = BigInt::from(p) % modulus;
+    let mut v = big_p;
+    let mut w = sub_small(ctx.square_mod(big_p), two, modulus);
+    for i in (0..(63 - k.leading_zeros())).rev() {
+        let product = sub_small(ctx.mul_mod(v, w), big_p, modulus);
+        if (k >> i) & 1 == 1 {
+            v = product;
+            w = sub_small(ctx.square_mod(w), two, modulus);
+        } else {
+            w = product;
+            v = sub_small(ctx.square_mod(v), two, modulus);
+        }
+    }
+
+    // u = u^2 - 2, n - 2 times
+    let mut u = v;
+    for _ in 0..(n - 2) {
+        u = sub_small(ctx.square_mod(u), two, modulus);
+    }
+
+    if u.is_zero() { PrimeResult::Prime } else { PrimeResult::Composite(Evidence::Sequence) }
+}
+
+
+
+// a witness a for the factor q of n - 1, None if a^(n-1) != 1 for one of
+// the tried bases, which means n is composite
+fn n_minus_one_witness(ctx: &BarrettCtx, q: BigInt) -> Option<BigInt> {
     let one = BigInt::from(1);
+    let n = ctx.modulus;
+    let n_minus_1 = n - one;
+
+    for a in 2..1000 {
+        let a = BigInt::from(a);
+        if a >= n_minus_1 {
+            return None;
+        }
+        if ctx.pow_mod(a, n_minus_1) != one {
+            return None;
+        }
+        let b = ctx.pow_mod(a, n_minus_1 / q);
+        if !b.is_zero() && utils::gcd(b - one, n) == one {
+            return Some(a);
+        }
+    }
+    None
+}
 
-    let mut s = zero;
-    let n_minus_1 = n.decrease();
 
-    let mut d = n_minus_1;
-    while d.is_even() {
-        d >>= 1;
-        s = s.increase();
+// factors n - 1 by the small primes, and also uses the cofactor if it is
+// prime itself. with `partial` the cofactor may stay unfactored as long as
+// the factored part is larger than the cube root of n
+fn n_minus_one_certificate(n: BigInt, partial: bool) -> Option<Certificate> {
+    if n.bits() <= 32 {
+        let n = n.chunks[0];
+        return if crate::small_is_prime(n) { Some(Certificate::Small(n)) } else { None };
+    }
+    if n.is_even() || n.bits() > 1024 || miller_rabin_test(n, 10).is_composite() {
+        return None;
     }
 
-    let mut bytes = [0; (1024 / 16)];
-    let mut x;
-    let mut base;
+    let one = BigInt::from(1);
+    let mut cofactor = n - one;
+    let mut factors = Vec::new();
+    for prime in [2].iter().chain(sieving_primes(64).iter()) {
+        let mut exponent = 0;
+        while cofactor.rem_u64(*prime) == 0 {
+            cofactor /= BigInt::from(*prime as u128);
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((BigInt::from(*prime as u128), exponent, Certificate::Small(*prime)));
+        }
+    }
 
-    'main_loop: for _ in 0..k {
+    if cofactor != one {
+        let cofactor_is_prime = if cofactor.bits() <= 32 {
+            crate::small_is_prime(cofactor.chunks[0])
+        } else {
+            miller_rabin_test(cofactor, 10) == PrimeResult::ProbablePrime
+        };
+
+        match cofactor_is_prime {
+            true => {
+                let certificate = n_minus_one_certificate(cofactor, partial)?;
+                factors.push((cofactor, 1, certificate));
+            }
+            false => {
+                let factored = (n - one) / cofactor;
+                if !partial || factored <= utils::iroot(n, 3) {
+                    return None;
+                }
+            }
+        }
+    }
 
-        rng::insert_random_bytes(&mut bytes).unwrap();
-        base = BigInt::from(bytes.as_slice());
+    let ctx = BarrettCtx::new(n);
+    let mut proofs = Vec::new();
+    for (q, exponent, certificate) in factors {
+        let witness = n_minus_one_witness(&ctx, q)?;
+        proofs.push(FactorProof { q, exponent, witness, certificate });
+    }
+    Some(Certificate::NMinusOne { n, factors: proofs })
+}
 
-        x = one;
-        while !d.is_zero() {
-            if !d.is_even() {
-                x = (x * base) % n;
+
+// pratt certificate, n - 1 and the n - 1 of every prime in it are fully
+// factored. None if n is composite or a factorization could not be found
+pub fn pratt_certificate(n: BigInt) -> Option<Certificate> {
+    n_minus_one_certificate(n, false)
+}
+
+
+// pocklington or brillhart-lehmer-selfridge certificate, needs the factored
+// part of n - 1 to be larger than the cube root of n
+pub fn pocklington_certificate(n: BigInt) -> Option<Certificate> {
+    n_minus_one_certificate(n, true)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lucas_lehmer_exponents() {
+        // every exponent up to the largest listed one
+        for p in 2..=1279 {
+            let result = lucas_lehmer(p);
+            if MERSENNE_PRIME_EXPONENTS.contains(&p) {
+                assert_eq!(result, PrimeResult::Prime, "2^{} - 1", p);
+            } else if crate::small_is_prime(p as u64) {
+                assert_eq!(result, PrimeResult::Composite(Evidence::Sequence), "2^{} - 1", p);
+            } else {
+                assert!(result.is_composite(), "2^{} - 1", p);
             }
-            d = d >> 1;
-            base = (base * base) % n;
         }
+    }
 
-        if x == one || x == n_minus_1 { continue 'main_loop; }
+    #[test]
+    fn proth_threes() {
+        // 3 * 2^n + 1 is prime for these n, and composite for the others
+        let exponents = [2, 5, 6, 8, 12, 18, 30, 36, 41, 66, 189, 201, 209, 276, 353, 408, 438, 534];
+        for n in 2..=600 {
+            let result = proth_test(3, n);
+            if exponents.contains(&n) {
+                assert_eq!(result, PrimeResult::Prime, "3 * 2^{} + 1", n);
+            } else {
+                assert!(result.is_composite(), "3 * 2^{} + 1", n);
+            }
+        }
+    }
 
-        while !s.is_zero() {
-            x = (x * x) % n;
-            if x == n_minus_1 { continue 'main_loop; }
-            s = s.decrease();
+    #[test]
+    fn proth_matches_miller_rabin() {
+        for _ in 0..300 {
+            let n = 2 + rng::u64() as usize % 200;
+            let k = (rng::u64() >> (64 - n.min(63))).max(1);
+            let p = (BigInt::from(k as u128) << n) + BigInt::from(1);
+            let expected = !miller_rabin_test(p, 20).is_composite();
+            assert_eq!(proth_test(k, n) == PrimeResult::Prime, expected, "{} * 2^{} + 1", k, n);
         }
+    }
 
-        return PrimeResult::Composite;
+    #[test]
+    #[should_panic(expected = "0 < k < 2^n")]
+    fn proth_rejects_large_multipliers() {
+        proth_test(8, 3);
     }
 
-    PrimeResult::ProbablePrime
+    #[test]
+    fn pepin_fermat_numbers() {
+        for m in 0..=10 {
+            let result = pepin_test(m);
+            if m <= 4 {
+                assert_eq!(result, PrimeResult::Prime, "F{}", m);
+            } else {
+                assert_eq!(result, PrimeResult::Composite(Evidence::EulerWitness(BigInt::from(3))), "F{}", m);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 10")]
+    fn pepin_rejects_large_indices() {
+        pepin_test(11);
+    }
+
+    #[test]
+    fn llr_small_riesel_numbers() {
+        // the list is complete for these k, so every other n is composite
+        for k in [3, 5, 7, 15] {
+            for n in 2..=300 {
+                if n < 64 && k >> n != 0 {
+                    continue;
+                }
+                let result = llr_test(k, n);
+                if RIESEL_PRIMES.contains(&(k, n)) {
+                    assert_eq!(result, PrimeResult::Prime, "{} * 2^{} - 1", k, n);
+                } else {
+                    assert!(result.is_composite(), "{} * 2^{} - 1", k, n);
+                }
+            }
+        }
+        // 3 * 2^5 - 1 = 5 * 19 and 7 * 2^4 - 1 = 3 * 37
+        assert!(llr_test(3, 5).is_composite());
+        assert!(llr_test(7, 4).is_composite());
+    }
+
+    fn is_proper_factor(result: &PrimeResult, n: BigInt) -> bool {
+        match result {
+            PrimeResult::Composite(Evidence::Factor(g)) => *g > BigInt::from(1) && *g < n && (n % *g).is_zero(),
+            result => result.is_composite(),
+        }
+    }
+
+    #[test]
+    fn solovay_strassen_u128_above_64_bits() {
+        let one = 1u128;
+        for p in [61, 89, 107, 127] {
+            let n = (one << p) - 1;
+            assert_eq!(solovay_strassen_test_u128(n, 20), PrimeResult::ProbablePrime);
+        }
+        // (2^61 - 1)(2^64 - 59) and (2^89 - 1)(2^31 - 1)
+        for n in [((one << 61) - 1) * ((one << 64) - 59), ((one << 89) - 1) * ((one << 31) - 1)] {
+            let result = solovay_strassen_test_u128(n, 20);
+            assert!(is_proper_factor(&result, BigInt::from(n)));
+        }
+    }
+
+    #[test]
+    fn frobenius_factors_are_proper() {
+        let one = BigInt::from(1);
+        for p in [61, 89, 127, 521] {
+            let n = (one << p) - one;
+            assert_eq!(frobenius_test(n, 5), PrimeResult::ProbablePrime);
+        }
+        // the sieving primes end at 48611, so trial division finds 2053 and
+        // 2063 but the other factors are left to the frobenius rounds
+        let primes = [2053u128, 2063, 48619, 65537, 1000003, (1 << 31) - 1, (1 << 61) - 1];
+        for p in primes {
+            for q in primes {
+                let n = BigInt::from(p * q);
+                assert!(is_proper_factor(&frobenius_test(n, 5), n), "{} * {}", p, q);
+            }
+        }
+    }
+
+    // the evidence has to hold up on its own, without redoing the test
+    fn check_evidence(evidence: Evidence, n: BigInt) {
+        let one = BigInt::from(1);
+        let n_minus_1 = n - one;
+        let ctx = BarrettCtx::new(n);
+        let is_base = |a: BigInt| a > one && a < n_minus_1;
+        match evidence {
+            Evidence::Factor(d) => assert!(d > one && d < n && (n % d).is_zero()),
+            Evidence::FermatWitness(a) => assert!(is_base(a) && ctx.pow_mod(a, n_minus_1) != one),
+            Evidence::EulerWitness(a) => {
+                let expected = match utils::jacobi(a, n) {
+                    1 => one,
+                    -1 => n_minus_1,
+                    _ => BigInt::zero(),
+                };
+                assert!(is_base(a) && ctx.pow_mod(a, n_minus_1 >> 1) != expected);
+            }
+            Evidence::SquareRoot(x) => {
+                assert!(is_base(x) && ctx.square_mod(x) == one);
+                let d = evidence.factor(n).unwrap();
+                assert!(d > one && d < n && (n % d).is_zero());
+            }
+            Evidence::BelowTwo | Evidence::Sequence => panic!("{:?} for {:?}", evidence, n),
+        }
+    }
+
+    fn evidence(result: PrimeResult) -> Evidence {
+        match result {
+            PrimeResult::Composite(evidence) => evidence,
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn evidence_is_checkable() {
+        let composites = [561u128, 41041, 2047, 3215031751, 1000003 * 1000033, 65537 * ((1 << 61) - 1)];
+        for n in composites {
+            let big_n = BigInt::from(n);
+            for _ in 0..50 {
+                check_evidence(evidence(miller_rabin_test(big_n, 20)), big_n);
+                check_evidence(evidence(miller_rabin_test_u128(n, 20)), big_n);
+                check_evidence(evidence(solovay_strassen_test(big_n, 20)), big_n);
+                // the carmichael number 3215031751 has no small factor for a
+                // fermat base to hit
+                if n != 3215031751 {
+                    check_evidence(evidence(fermat_test(big_n, 64)), big_n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn carmichael_square_roots() {
+        // a base coprime to a carmichael number passes the fermat test, so
+        // miller-rabin can only reject it with a square root of 1
+        for n in [561u128, 41041, 3215031751] {
+            let big_n = BigInt::from(n);
+            let mut square_roots = 0;
+            for _ in 0..50 {
+                let PrimeResult::Composite(evidence) = miller_rabin_test(big_n, 1) else { continue };
+                if let Evidence::SquareRoot(_) = evidence {
+                    square_roots += 1;
+                    let d = evidence.factor(big_n).unwrap();
+                    assert!(d > BigInt::from(1) && d < big_n && (big_n % d).is_zero());
+                }
+            }
+            assert!(square_roots > 0, "{}", n);
+        }
+    }
+
+    #[test]
+    fn trial_division_factors() {
+        for (n, p) in [(561, 3), (41041, 7), (1000003 * 1000033, 1000003), (7 * 7, 7), (25, 5)] {
+            assert_eq!(trial_division(n, 5), PrimeResult::Composite(Evidence::Factor(BigInt::from(p))));
+        }
+        assert_eq!(trial_division(1000003, 5), PrimeResult::Prime);
+        assert_eq!(Evidence::Factor(BigInt::from(3)).factor(BigInt::from(561)), Some(BigInt::from(3)));
+        assert_eq!(Evidence::FermatWitness(BigInt::from(2)).factor(BigInt::from(561)), None);
+    }
 }
@@ -0,0 +1,190 @@
+use crate::bigint::{
+    BigInt, N, bigint_div, mul_limbs, sqr_limbs, limbs_sub_assign,
+};
+
+
+// precomputed state for reducing many numbers by the same modulus n.
+// with k = number of chunks in n and b = 2^64, mu = floor(b^2k / n)
+#[derive(Clone, Copy)]
+pub struct BarrettCtx {
+    pub modulus: BigInt,
+    mu: BigInt,
+    k: usize,
+}
+
+
+impl BarrettCtx {
+
+    pub fn new(modulus: BigInt) -> Self {
+        if modulus.is_zero() { panic!("Attempt to divide by zero"); }
+
+        let k = modulus.size() + 1;
+        if 2 * k > N { panic!("Modulus too large for barrett reduction"); }
+
+        // b^2k does not fit in a BigInt when k = N / 2, so divide it by
+        // the modulus one chunk at a time, starting from b^k
+        let (high, mut remainder) = bigint_div(BigInt::from(1) << (64 * k), modulus);
+        let mut mu = high << (64 * k);
+        let mut digit;
+        for i in (0..k).rev() {
+            (digit, remainder) = bigint_div(remainder << 64, modulus);
+            mu.chunks[i] = digit.chunks[0];
+        }
+
+        // mu only needs k + 2 chunks when n = b^(k-1), use b^(k+1) - 1
+        // instead, which costs at most one extra subtraction in reduce()
+        if mu.size() > k {
+            mu = BigInt::zero();
+            mu.chunks[..(k + 1)].fill(u64::MAX);
+        }
+
+        Self { modulus, mu, k }
+    }
+
+
+    pub fn reduce(&self, x: BigInt) -> BigInt {
+        let k = self.k;
+        if x.size() >= 2 * k { return x % self.modulus }
+
+        // q = floor(floor(x / b^(k-1)) * mu / b^(k+1))
+        let mut wide = [0; 2 * N];
+        mul_limbs(&x.chunks[(k - 1)..(2 * k)], &self.mu.chunks[..(k + 1)], &mut wide);
+        let mut q = BigInt::zero();
+        q.chunks[..(k + 1)].copy_from_slice(&wide[(k + 1)..(2 * k + 2)]);
+
+        // r = (x - q * n) mod b^(k+1), which is less than 3n
+        mul_limbs(&q.chunks[..(k + 1)], &self.modulus.chunks[..k], &mut wide);
+        let mut r = BigInt::zero();
+        r.chunks[..(k + 1)].copy_from_slice(&x.chunks[..(k + 1)]);
+        limbs_sub_assign(&mut r.chunks[..(k + 1)], &wide[..(k + 1)]);
+
+        while r >= self.modulus {
+            r -= self.modulus;
+        }
+        r
+    }
+
+
+    pub fn mul_mod(&self, a: BigInt, b: BigInt) -> BigInt {
+        self.reduce(a * b)
+    }
+
+
+    pub fn square_mod(&self, a: BigInt) -> BigInt {
+        let mut wide = [0; 2 * N];
+        let mut result = BigInt::zero();
+        let len = a.size() + 1;
+        sqr_limbs(&a.chunks[..len], &mut wide);
+        result.chunks.copy_from_slice(&wide[..N]);
+        self.reduce(result)
+    }
+
+
+    pub fn pow_mod(&self, base: BigInt, exponent: BigInt) -> BigInt {
+        let base = self.reduce(base);
+        let mut result = self.reduce(BigInt::from(1));
+
+        for i in (0..exponent.bits()).rev() {
+            result = self.square_mod(result);
+            if exponent.bit(i) {
+                result = self.mul_mod(result, base);
+            }
+        }
+        result
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::rng;
+    use super::*;
+
+    fn random_chunks(len: usize) -> BigInt {
+        let mut num = BigInt::zero();
+        for chunk in num.chunks[..len].iter_mut() {
+            *chunk = rng::u64();
+        }
+        num
+    }
+
+    // random moduli of every width up to 1024 bits, plus the edge cases:
+    // all ones (leading chunk u64::MAX), powers of b, and the full 16 chunks
+    fn moduli() -> Vec<BigInt> {
+        let one = BigInt::from(1);
+        let mut moduli = vec![one, BigInt::from(2), BigInt::from(3), BigInt::from(u64::MAX as u128)];
+        for k in 1..=(N / 2) {
+            let mut modulus = random_chunks(k);
+            modulus.chunks[k - 1] |= 1;
+            moduli.push(modulus);
+            moduli.push((one << (64 * k)) - one);
+            moduli.push(one << (64 * (k - 1)));
+            let mut leading_max = random_chunks(k);
+            leading_max.chunks[k - 1] = u64::MAX;
+            moduli.push(leading_max);
+        }
+        moduli.push((one << 1023) + one);
+        let mut full = BigInt::random();
+        full.modify();
+        moduli.push(full);
+        moduli
+    }
+
+    #[test]
+    fn reduce_matches_division() {
+        for modulus in moduli() {
+            let ctx = BarrettCtx::new(modulus);
+            let k = modulus.size() + 1;
+            for len in [1, k, 2 * k - 1, 2 * k, N] {
+                for _ in 0..10 {
+                    let x = random_chunks(len);
+                    assert_eq!(ctx.reduce(x), x % modulus, "{:?} mod {:?}", x, modulus);
+                }
+            }
+            // b^2k - 1, the largest input of the barrett path
+            let mut largest = BigInt::zero();
+            largest.chunks[..(2 * k)].fill(u64::MAX);
+            assert_eq!(ctx.reduce(largest), largest % modulus);
+        }
+    }
+
+    #[test]
+    fn mul_and_square_match_division() {
+        for modulus in moduli() {
+            let ctx = BarrettCtx::new(modulus);
+            let k = modulus.size() + 1;
+            let below_max = modulus - BigInt::from(1);
+            for _ in 0..10 {
+                let a = random_chunks(k) % modulus;
+                let b = random_chunks(k) % modulus;
+                assert_eq!(ctx.mul_mod(a, b), (a * b) % modulus);
+                assert_eq!(ctx.square_mod(a), a.square() % modulus);
+            }
+            assert_eq!(ctx.mul_mod(below_max, below_max), (below_max * below_max) % modulus);
+            assert_eq!(ctx.square_mod(below_max), below_max.square() % modulus);
+        }
+    }
+
+    #[test]
+    fn pow_mod_matches_repeated_multiplication() {
+        for modulus in moduli() {
+            let ctx = BarrettCtx::new(modulus);
+            let base = random_chunks(modulus.size() + 1) % modulus;
+            let mut expected = BigInt::from(1) % modulus;
+            for e in 0..40u128 {
+                assert_eq!(ctx.pow_mod(base, BigInt::from(e)), expected, "{:?}^{} mod {:?}", base, e, modulus);
+                expected = (expected * base) % modulus;
+            }
+        }
+
+        // fermat's little theorem for mersenne primes up to 607 bits
+        let one = BigInt::from(1);
+        for p in [61, 89, 127, 521, 607] {
+            let prime = (one << p) - one;
+            let ctx = BarrettCtx::new(prime);
+            let base = random_chunks(8) % prime;
+            assert_eq!(ctx.pow_mod(base, prime - one), one);
+        }
+    }
+}
@@ -9,7 +9,7 @@ use std::cmp::Ordering;
 use crate::rng::insert_random_bytes;
 
 
-const N: usize = 2048 / 64;
+pub const N: usize = 2048 / 64;
 
 
 #[derive(Clone, Copy)]
@@ -42,6 +42,56 @@ impl BigInt {
         self.chunks[(N / 2) - 1] |= 0x8000000000000000u64;
         self.chunks[0] |= 1;
     }
+
+    // random odd number with exactly `bits` bits, assumption: bits <= 1024
+    pub fn random_bits(bits: usize) -> Self {
+        let mut num = BigInt::random() >> (1024 - bits);
+        num.set_bit(bits - 1);
+        num.set_bit(0);
+        num
+    }
+
+    // None unless the text is a non-empty hex number that fits in N chunks
+    pub fn from_hex(text: &str) -> Option<Self> {
+        let digits = text.trim_start_matches('0');
+        if text.is_empty() || digits.len() > N * 16 {
+            return None;
+        }
+
+        let mut num = BigInt::zero();
+        for (i, digit) in digits.bytes().rev().enumerate() {
+            let value = (digit as char).to_digit(16)? as u64;
+            num.chunks[i / 16] |= value << (4 * (i % 16));
+        }
+        Some(num)
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        self.chunks[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn set_bit(&mut self, i: usize) {
+        self.chunks[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn rem_u64(&self, modulus: u64) -> u64 {
+        let mut remainder = 0;
+        for chunk in self.chunks.iter().take(self.size() + 1).rev() {
+            remainder = ((remainder << 64) + *chunk as u128) % modulus as u128;
+        }
+        remainder as u64
+    }
+
+    pub fn div_rem_u64(&self, divisor: u64) -> (BigInt, u64) {
+        let mut quotient = BigInt::zero();
+        let mut remainder = 0;
+        for i in (0..(self.size() + 1)).rev() {
+            let current = (remainder << 64) + self.chunks[i] as u128;
+            quotient.chunks[i] = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (quotient, remainder as u64)
+    }
 }
 
 
@@ -88,9 +138,28 @@ impl fmt::Display for BigInt {
 }
 
 
+impl fmt::LowerHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let top = self.size();
+        let mut output = format!("{:x}", self.chunks[top]);
+        for chunk in self.chunks[..top].iter().rev() {
+            output.push_str(format!("{:016x}", chunk).as_str());
+        }
+        write!(f, "{}", output)
+    }
+}
+
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:x}", self)
+    }
+}
+
+
 impl BigInt {
     pub fn print_decimal(&self) {
-        let mut bnum = self.clone();
+        let mut bnum = *self;
         let mut scratch = BigInt::zero();
 
 
@@ -252,33 +321,257 @@ impl BigInt {
 }
 
 
-fn bigint_mul(own: BigInt, other: BigInt) -> BigInt {
-    let mut result = BigInt::zero();
+// acc += other, returns the carry out of acc
+pub fn limbs_add_assign(acc: &mut [u64], other: &[u64]) -> u64 {
+    let mut carry = 0;
+    let mut sum_overflow;
+    let mut carry_overflow;
+    let (low, high) = acc.split_at_mut(other.len());
+
+    for (chunk1, chunk2) in low.iter_mut().zip(other.iter()) {
+        (*chunk1, sum_overflow) = chunk1.overflowing_add(*chunk2);
+        (*chunk1, carry_overflow) = chunk1.overflowing_add(carry);
+        carry = sum_overflow as u64 + carry_overflow as u64;
+    }
+    for chunk in high.iter_mut() {
+        (*chunk, carry_overflow) = chunk.overflowing_add(carry);
+        carry = carry_overflow as u64;
+    }
+    carry
+}
+
+
+// acc -= other, returns the borrow out of acc
+pub fn limbs_sub_assign(acc: &mut [u64], other: &[u64]) -> u64 {
+    let mut borrow = 0;
+    let mut diff_overflow;
+    let mut borrow_overflow;
+    let (low, high) = acc.split_at_mut(other.len());
+
+    for (chunk1, chunk2) in low.iter_mut().zip(other.iter()) {
+        (*chunk1, diff_overflow) = chunk1.overflowing_sub(*chunk2);
+        (*chunk1, borrow_overflow) = chunk1.overflowing_sub(borrow);
+        borrow = diff_overflow as u64 + borrow_overflow as u64;
+    }
+    for chunk in high.iter_mut() {
+        (*chunk, borrow_overflow) = chunk.overflowing_sub(borrow);
+        borrow = borrow_overflow as u64;
+    }
+    borrow
+}
+
+
+// out = |a - b| where b may be shorter than a, returns true if a < b
+fn limbs_abs_diff(a: &[u64], b: &[u64], out: &mut [u64]) -> bool {
+    out.copy_from_slice(a);
+    if limbs_sub_assign(out, b) == 0 { return false }
+
+    // a < b, so negate the two's complement result
+    let mut carry = true;
+    for chunk in out.iter_mut() {
+        (*chunk, carry) = (!*chunk).overflowing_add(carry as u64);
+    }
+    true
+}
+
+
+pub fn mul_schoolbook(a: &[u64], b: &[u64], out: &mut [u64]) {
     let mut intermediate;
     let mut carry;
+    out[..(a.len() + b.len())].fill(0);
 
-    let t = own.size();
-    let n = other.size();
-    if t + n + 1 >= N { panic!("Attempt to multiply with overflow"); }
-
-    for (j, chunk2) in other.chunks.iter().take(n + 1).enumerate() {
-        if *chunk2 == 0 { continue; }
+    for (j, chunk2) in b.iter().enumerate() {
         carry = 0;
+        for (chunk1, result) in a.iter().zip(out[j..].iter_mut()) {
+            intermediate = ((*chunk1 as u128) * (*chunk2 as u128)) + carry;
+            intermediate += *result as u128;
+            *result = intermediate as u64;
+            carry = intermediate >> 64;
+        }
+        out[a.len() + j] = carry as u64;
+    }
+}
+
 
-        for (i, chunk1) in own.chunks.iter().take(t + 1).enumerate() {
-            if *chunk1 == 0 && carry == 0 { continue; }
+pub fn sqr_schoolbook(a: &[u64], out: &mut [u64]) {
+    let n = a.len();
+    let mut intermediate;
+    let mut carry;
+    out[..(2 * n)].fill(0);
 
+    // every cross product a[i] * a[j] with i < j appears twice
+    for (i, chunk1) in a.iter().enumerate() {
+        carry = 0;
+        for (chunk2, result) in a[(i + 1)..].iter().zip(out[(2 * i + 1)..].iter_mut()) {
             intermediate = ((*chunk1 as u128) * (*chunk2 as u128)) + carry;
-            intermediate += result.chunks[i + j] as u128;
-            result.chunks[i + j] = intermediate as u64;
+            intermediate += *result as u128;
+            *result = intermediate as u64;
             carry = intermediate >> 64;
         }
-        result.chunks[t + j + 1] += carry as u64;
+        out[i + n] = carry as u64;
+    }
+
+    let mut overflow = 0;
+    for chunk in out[..(2 * n)].iter_mut() {
+        (*chunk, overflow) = ((*chunk << 1) | overflow, *chunk >> 63);
+    }
+
+    carry = 0;
+    for (chunk, result) in a.iter().zip(out.chunks_exact_mut(2)) {
+        intermediate = ((*chunk as u128) * (*chunk as u128)) + carry;
+        intermediate += result[0] as u128;
+        result[0] = intermediate as u64;
+        intermediate = (intermediate >> 64) + result[1] as u128;
+        result[1] = intermediate as u64;
+        carry = intermediate >> 64;
+    }
+}
+
+
+// karatsuba with the recursion stopping below `threshold` chunks.
+// benches/karatsuba.rs finds no operand length up to N chunks, the widest
+// this crate multiplies, where it beats schoolbook for any threshold, so
+// mul_limbs and sqr_limbs do not use it
+//
+// assumption: a and b have the same length, at most N chunks
+//
+// uses the subtractive form, a0*b1 + a1*b0 = z0 + z2 - (a0 - a1)(b0 - b1),
+// so that the middle product stays the same size as the halves
+pub fn mul_karatsuba(a: &[u64], b: &[u64], out: &mut [u64], threshold: usize) {
+    let n = a.len();
+    if n > N { panic!("Karatsuba operands must be at most {} chunks", N); }
+    if n < threshold.max(2) {
+        return mul_schoolbook(a, b, out);
+    }
+
+    let m = n / 2;
+    let h = n - m;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+
+    // z0 = a0 * b0 and z2 = a1 * b1 go straight to their place in out
+    mul_karatsuba(a0, b0, &mut out[..(2 * m)], threshold);
+    mul_karatsuba(a1, b1, &mut out[(2 * m)..(2 * n)], threshold);
+
+    let mut a_diff = [0; N / 2];
+    let mut b_diff = [0; N / 2];
+    let a_negative = limbs_abs_diff(a1, a0, &mut a_diff[..h]);
+    let b_negative = limbs_abs_diff(b1, b0, &mut b_diff[..h]);
+
+    let mut middle = [0; N];
+    mul_karatsuba(&a_diff[..h], &b_diff[..h], &mut middle[..(2 * h)], threshold);
+
+    // z1 = z0 + z2 - (a1 - a0)(b1 - b0), negated as needed
+    let mut z1 = [0; N + 1];
+    let z1 = &mut z1[..(2 * h + 1)];
+    z1[..(2 * m)].copy_from_slice(&out[..(2 * m)]);
+    limbs_add_assign(z1, &out[(2 * m)..(2 * n)]);
+    if a_negative == b_negative {
+        limbs_sub_assign(z1, &middle[..(2 * h)]);
+    } else {
+        limbs_add_assign(z1, &middle[..(2 * h)]);
+    }
+
+    limbs_add_assign(&mut out[m..(2 * n)], z1);
+}
+
+
+// assumption: a has at most N chunks
+pub fn sqr_karatsuba(a: &[u64], out: &mut [u64], threshold: usize) {
+    let n = a.len();
+    if n > N { panic!("Karatsuba operands must be at most {} chunks", N); }
+    if n < threshold.max(2) {
+        return sqr_schoolbook(a, out);
+    }
+
+    let m = n / 2;
+    let h = n - m;
+    let (a0, a1) = a.split_at(m);
+
+    sqr_karatsuba(a0, &mut out[..(2 * m)], threshold);
+    sqr_karatsuba(a1, &mut out[(2 * m)..(2 * n)], threshold);
+
+    let mut a_diff = [0; N / 2];
+    limbs_abs_diff(a1, a0, &mut a_diff[..h]);
+
+    let mut middle = [0; N];
+    sqr_karatsuba(&a_diff[..h], &mut middle[..(2 * h)], threshold);
+
+    // z1 = z0 + z2 - (a1 - a0)^2
+    let mut z1 = [0; N + 1];
+    let z1 = &mut z1[..(2 * h + 1)];
+    z1[..(2 * m)].copy_from_slice(&out[..(2 * m)]);
+    limbs_add_assign(z1, &out[(2 * m)..(2 * n)]);
+    limbs_sub_assign(z1, &middle[..(2 * h)]);
+
+    limbs_add_assign(&mut out[m..(2 * n)], z1);
+}
+
+
+// full product of two chunk slices, out needs a.len() + b.len() chunks
+pub fn mul_limbs(a: &[u64], b: &[u64], out: &mut [u64]) {
+    mul_schoolbook(a, b, out);
+}
+
+
+pub fn sqr_limbs(a: &[u64], out: &mut [u64]) {
+    sqr_schoolbook(a, out);
+}
+
+
+// the N chunks of limbs >> amount starting at the lowest one, for splitting
+// wide products
+pub fn limbs_shr(limbs: &[u64], amount: usize) -> BigInt {
+    let offset = amount / 64;
+    let shift = amount % 64;
+    let mut result = BigInt::zero();
+    for (i, chunk) in result.chunks.iter_mut().enumerate() {
+        let lower = *limbs.get(i + offset).unwrap_or(&0);
+        *chunk = match shift {
+            0 => lower,
+            _ => (lower >> shift) | (limbs.get(i + offset + 1).unwrap_or(&0) << (64 - shift)),
+        };
     }
     result
 }
 
 
+fn bigint_mul(own: BigInt, other: BigInt) -> BigInt {
+    let mut result = BigInt::zero();
+    let mut wide = [0; 2 * N];
+
+    let t = own.size();
+    let n = other.size();
+    if t + n + 1 >= N { panic!("Attempt to multiply with overflow"); }
+
+    mul_limbs(&own.chunks[..(t + 1)], &other.chunks[..(n + 1)], &mut wide);
+
+    result.chunks.copy_from_slice(&wide[..N]);
+    result
+}
+
+
+fn bigint_sqr(own: BigInt) -> BigInt {
+    let mut result = BigInt::zero();
+    let mut wide = [0; 2 * N];
+
+    let t = own.size();
+    if 2 * t + 1 >= N { panic!("Attempt to multiply with overflow"); }
+
+    sqr_limbs(&own.chunks[..(t + 1)], &mut wide);
+
+    result.chunks.copy_from_slice(&wide[..N]);
+    result
+}
+
+
+impl BigInt {
+    pub fn square(self) -> BigInt {
+        bigint_sqr(self)
+    }
+}
+
+
 impl Mul for BigInt {
     type Output = Self;
     fn mul(self, other: Self) -> Self {
@@ -295,14 +588,17 @@ impl MulAssign for BigInt {
 
 
 fn bigint_shl(own: BigInt, amount: usize) -> BigInt {
-    debug_assert!(amount <= 128);
     let mut result = BigInt::zero();
     let mut overflow = 0;
     let mut shifted: u128;
 
-    for (i, chunk) in own.chunks.iter().enumerate() {
+    let offset = amount / 64;
+    let amount = amount % 64;
+    if offset >= N { return result }
+
+    for (i, chunk) in own.chunks.iter().take(N - offset).enumerate() {
         shifted = ((*chunk as u128) << amount) + overflow;
-        result.chunks[i] = shifted as u64;
+        result.chunks[i + offset] = shifted as u64;
         overflow = shifted >> 64;
     }
 
@@ -326,15 +622,18 @@ impl ShlAssign<usize> for BigInt {
 
 
 fn bigint_shr(own: BigInt, amount: usize) -> BigInt {
-    debug_assert!(amount <= 128);
     let mut result = BigInt::zero();
     let mut overflow = 0;
     let mut shifted: u128;
 
-    for (i, chunk) in own.chunks.iter().enumerate().rev() {
+    let offset = amount / 64;
+    let amount = amount % 64;
+    if offset >= N { return result }
+
+    for (i, chunk) in own.chunks.iter().enumerate().skip(offset).rev() {
         shifted = ((*chunk as u128) << (64 - amount)) + (overflow << 64);
         overflow = (shifted as u64) as u128;
-        result.chunks[i] = (shifted >> 64) as u64;
+        result.chunks[i - offset] = (shifted >> 64) as u64;
     }
 
     result
@@ -374,19 +673,37 @@ impl PartialOrd for BigInt {
 
 impl BigInt {
 
-    fn size(&self) -> usize {
+    pub fn size(&self) -> usize {
         let mut n = N - 1;
         for chunk in self.chunks.iter().rev() {
-            if *chunk != 0 { break; }
+            if *chunk != 0 || n == 0 { break; }
             n -= 1;
         }
         n
     }
 
+    pub fn bits(&self) -> usize {
+        for (i, chunk) in self.chunks.iter().enumerate().rev() {
+            if *chunk != 0 {
+                return (i * 64) + 64 - chunk.leading_zeros() as usize;
+            }
+        }
+        0
+    }
+
+    pub fn trailing_zeros(&self) -> usize {
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if *chunk != 0 {
+                return (i * 64) + chunk.trailing_zeros() as usize;
+            }
+        }
+        N * 64
+    }
+
 }
 
 
-fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
+pub fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
     if divisor.is_zero() { panic!("Attempt to divide by zero"); }
     if dividend < divisor { return (BigInt::zero(), dividend) }
 
@@ -402,6 +719,10 @@ fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
         while divisor.chunks[t] << lambda < u64::MAX / 2 {
             lambda += 1;
         }
+        // the shifted dividend would need one chunk more than N
+        if dividend.chunks[N - 1] >> (64 - lambda) != 0 {
+            return div_full_width(dividend, divisor);
+        }
         divisor <<= lambda;
         dividend <<= lambda;
     }
@@ -412,7 +733,7 @@ fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
     if t == 0 {
         let divisor_digit = divisor.chunks[0] as u128;
         let mut remainder = 0;
-        let mut current = 0;
+        let mut current;
 
         for (i, chunk) in dividend.chunks.iter().enumerate().rev().skip(N - n - 1) {
             current = (remainder << 64) + *chunk as u128;
@@ -423,7 +744,7 @@ fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
     }
 
     // step 2, align and then subtract y from x until x >= aligned
-    let mut aligned = divisor.clone();
+    let mut aligned = divisor;
     for _ in 0..(n - t) {
         aligned <<= 64;
     }
@@ -446,7 +767,7 @@ fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
 
         // step 3.1
         if dividend.chunks[i] == divisor.chunks[t] {
-            q_digit.chunks[0] = u64::MAX - 1;
+            q_digit.chunks[0] = u64::MAX;
         } else {
             q_u128 = (dividend.chunks[i] as u128) << 64;
             q_u128 += dividend.chunks[i - 1] as u128;
@@ -472,7 +793,7 @@ fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
         quotient.chunks[i - t - 1] = q_digit.chunks[0];
 
         // precalc shifted y
-        let mut y_shifted = divisor.clone();
+        let mut y_shifted = divisor;
         for _ in 0..(i - t - 1) {
             y_shifted <<= 64;
         }
@@ -494,6 +815,33 @@ fn bigint_div(mut dividend: BigInt, mut divisor: BigInt) -> (BigInt, BigInt) {
 }
 
 
+// division of a dividend that uses the top chunk by a divisor that needs
+// normalising. with dividend = high * b + low, high and then
+// (high mod divisor) * b + low are divided separately, both fit after
+// shifting. a divisor of N chunks leaves a quotient below b, which is found
+// bit by bit instead
+fn div_full_width(dividend: BigInt, divisor: BigInt) -> (BigInt, BigInt) {
+    if divisor.size() + 2 <= N {
+        let (high, remainder) = bigint_div(dividend >> 64, divisor);
+        let mut rest = remainder << 64;
+        rest.chunks[0] = dividend.chunks[0];
+        let (low, remainder) = bigint_div(rest, divisor);
+        return ((high << 64) + low, remainder);
+    }
+
+    let mut quotient = BigInt::zero();
+    let mut remainder = dividend;
+    for bit in (0..=(dividend.bits() - divisor.bits())).rev() {
+        let shifted = divisor << bit;
+        if remainder >= shifted {
+            remainder -= shifted;
+            quotient.chunks[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+    (quotient, remainder)
+}
+
+
 impl Div for BigInt {
     type Output = Self;
     fn div(self, other: Self) -> Self {
@@ -522,3 +870,127 @@ impl RemAssign for BigInt {
         *self = bigint_div(*self, other).1;
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use crate::rng;
+    use super::*;
+
+    #[test]
+    fn shift_past_chunks() {
+        let num = rng::u64() as u128;
+        for amount in [0, 63, 64, 65, 127, 128, 200, 64 * N - 64] {
+            let shifted = BigInt::from(num) << amount;
+            assert!(shifted >> amount == BigInt::from(num));
+        }
+        assert!(BigInt::from(num) << (64 * N) == BigInt::zero());
+        assert!(BigInt::from(num) >> 128 == BigInt::zero());
+    }
+
+    // leading dividend and divisor digits are equal and the quotient digit
+    // is b - 1, as when 2^192 is reduced modulo 2^128 + 1
+    #[test]
+    fn rem_equal_leading_digits() {
+        let modulus = (BigInt::from(1) << 128) + BigInt::from(1);
+        let low = [0, 1, rng::u64() as u128, u64::MAX as u128];
+        for x in low.map(|low| (BigInt::from(1) << 192) + BigInt::from(low)) {
+            let (quotient, remainder) = bigint_div(x, modulus);
+            assert!(remainder < modulus);
+            assert!(quotient * modulus + remainder == x);
+        }
+        for _ in 0..1000 {
+            let x = (BigInt::from(rng::u128()) << 128) + BigInt::from(rng::u128());
+            assert!(x % modulus < modulus);
+        }
+    }
+
+    // random number with exactly `bits` bits, up to the full N chunks
+    fn random_wide(bits: usize) -> BigInt {
+        let mut num = BigInt::zero();
+        for chunk in num.chunks[..bits.div_ceil(64)].iter_mut() {
+            *chunk = rng::u64();
+        }
+        if !bits.is_multiple_of(64) {
+            num.chunks[bits / 64] &= (1 << (bits % 64)) - 1;
+        }
+        num.chunks[(bits - 1) / 64] |= 1 << ((bits - 1) % 64);
+        num
+    }
+
+    // dividends that use the top chunk, so normalising the divisor would
+    // shift bits out of the BigInt
+    #[test]
+    fn div_full_width_dividends() {
+        let sizes = [(1025, 1022), (1030, 1017), (2046, 1), (2040, 7), (1984, 63), (48, 2000), (1, 2047)];
+        for (quotient_bits, divisor_bits) in sizes {
+            for _ in 0..20 {
+                let quotient = random_wide(quotient_bits);
+                let divisor = random_wide(divisor_bits);
+                let remainder = if divisor_bits > 1 { random_wide(divisor_bits - 1) } else { BigInt::zero() };
+
+                let mut wide = [0; 2 * N];
+                mul_limbs(&quotient.chunks, &divisor.chunks, &mut wide);
+                let mut dividend = BigInt::zero();
+                dividend.chunks.copy_from_slice(&wide[..N]);
+                dividend += remainder;
+
+                let (q, r) = bigint_div(dividend, divisor);
+                assert!(q == quotient && r == remainder, "{:?} / {:?}", dividend, divisor);
+            }
+        }
+        for divisor in [3, 1 << 40, u64::MAX as u128 >> 1] {
+            let x = random_wide(64 * N);
+            assert!(x % BigInt::from(divisor) == BigInt::from(x.rem_u64(divisor as u64) as u128));
+        }
+    }
+
+    // all ones maximises every carry
+    fn operands(len: usize) -> Vec<Vec<u64>> {
+        vec![
+            (0..len).map(|_| rng::u64()).collect(),
+            vec![u64::MAX; len],
+            (0..len).map(|i| if i % 2 == 0 { u64::MAX } else { 0 }).collect(),
+        ]
+    }
+
+    #[test]
+    fn karatsuba_matches_schoolbook() {
+        for len in 1..=N {
+            for (a, b) in operands(len).iter().zip(operands(len).iter().rev()) {
+                let mut expected = vec![0; 2 * len];
+                mul_schoolbook(a, b, &mut expected);
+                for threshold in [2, 3, 4, 8, 16, 32] {
+                    let mut out = vec![0; 2 * len];
+                    mul_karatsuba(a, b, &mut out, threshold);
+                    assert_eq!(out, expected, "{} chunks, threshold {}", len, threshold);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn squaring_matches_schoolbook() {
+        for len in 1..=N {
+            for a in operands(len) {
+                let mut expected = vec![0; 2 * len];
+                mul_schoolbook(&a, &a, &mut expected);
+                let mut out = vec![0; 2 * len];
+                sqr_schoolbook(&a, &mut out);
+                assert_eq!(out, expected, "{} chunks", len);
+                for threshold in [2, 3, 4, 8, 16, 32] {
+                    sqr_karatsuba(&a, &mut out, threshold);
+                    assert_eq!(out, expected, "{} chunks, threshold {}", len, threshold);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 32 chunks")]
+    fn karatsuba_rejects_wide_operands() {
+        let a = vec![1; N + 1];
+        let mut out = vec![0; 2 * N + 2];
+        mul_karatsuba(&a, &a, &mut out, 2);
+    }
+}
@@ -0,0 +1,423 @@
+use crate::bigint::BigInt;
+use crate::barrett::BarrettCtx;
+use crate::ecpp::{Curve, Point, curve_factor_bound};
+use crate::utils;
+
+
+// proof that a number is prime. small primes are checked by trial division,
+// larger ones through a factored part f of n - 1 (pocklington / brillhart,
+// lehmer and selfridge) or an elliptic curve. a pratt certificate is the
+// case f = n - 1
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+pub enum Certificate {
+    Small(u64),
+    NMinusOne { n: BigInt, factors: Vec<FactorProof> },
+    EllipticCurve(Box<CurveStep>),
+}
+
+
+// q^exponent divides n - 1, witness^(n-1) = 1 mod n and
+// gcd(witness^((n-1)/q) - 1, n) = 1, and q is proven prime by `certificate`
+#[derive(Clone)]
+pub struct FactorProof {
+    pub q: BigInt,
+    pub exponent: u32,
+    pub witness: BigInt,
+    pub certificate: Certificate,
+}
+
+
+// (x, y) lies on y^2 = x^3 + ax + b modulo n, m * (x, y) = 0 while
+// (m / q) * (x, y) is not, and q is proven prime by `certificate`
+#[derive(Clone)]
+pub struct CurveStep {
+    pub n: BigInt,
+    pub a: BigInt,
+    pub b: BigInt,
+    pub x: BigInt,
+    pub y: BigInt,
+    pub m: BigInt,
+    pub q: BigInt,
+    pub certificate: Certificate,
+}
+
+
+// nesting limit for parsed certificates, so crafted text cannot run the
+// parser or the verifier out of stack. elliptic curve proofs of 1024 bit
+// primes nest about 40 levels deep, chains of pocklington steps about 10
+const MAX_DEPTH: usize = 128;
+
+
+impl Certificate {
+
+    pub fn prime(&self) -> BigInt {
+        match self {
+            Certificate::Small(p) => BigInt::from(*p as u128),
+            Certificate::NMinusOne { n, .. } => *n,
+            Certificate::EllipticCurve(step) => step.n,
+        }
+    }
+
+    // one item per line, every factor is followed by its own certificate:
+    //   small <p>
+    //   n-1 <n> <number of factors>
+    //   factor <q> <exponent> <witness>
+    //   curve <n> <a> <b> <x> <y> <m> <q>
+    // all numbers are hexadecimal except for the counts and exponents
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        self.write_text(&mut text);
+        text
+    }
+
+    fn write_text(&self, text: &mut String) {
+        match self {
+            Certificate::Small(p) => text.push_str(&format!("small {:x}\n", p)),
+            Certificate::NMinusOne { n, factors } => {
+                text.push_str(&format!("n-1 {:x} {}\n", n, factors.len()));
+                for factor in factors.iter() {
+                    text.push_str(&format!(
+                        "factor {:x} {} {:x}\n", factor.q, factor.exponent, factor.witness
+                    ));
+                    factor.certificate.write_text(text);
+                }
+            }
+            Certificate::EllipticCurve(step) => {
+                text.push_str(&format!(
+                    "curve {:x} {:x} {:x} {:x} {:x} {:x} {:x}\n",
+                    step.n, step.a, step.b, step.x, step.y, step.m, step.q
+                ));
+                step.certificate.write_text(text);
+            }
+        }
+    }
+
+    // None if the text is not a single well formed certificate
+    pub fn from_text(text: &str) -> Option<Certificate> {
+        let mut tokens = text.split_whitespace();
+        let certificate = Certificate::read_text(&mut tokens, MAX_DEPTH)?;
+        match tokens.next() {
+            Some(_) => None,
+            None => Some(certificate),
+        }
+    }
+
+    fn read_text<'a, I: Iterator<Item = &'a str>>(tokens: &mut I, depth: usize) -> Option<Certificate> {
+        if depth == 0 {
+            return None;
+        }
+        match tokens.next()? {
+            "small" => Some(Certificate::Small(u64::from_str_radix(tokens.next()?, 16).ok()?)),
+            "n-1" => Certificate::read_n_minus_one(tokens, depth),
+            "curve" => Certificate::read_curve(tokens, depth),
+            _ => None,
+        }
+    }
+
+    fn read_n_minus_one<'a, I: Iterator<Item = &'a str>>(tokens: &mut I, depth: usize) -> Option<Certificate> {
+        let n = BigInt::from_hex(tokens.next()?)?;
+        let count: usize = tokens.next()?.parse().ok()?;
+        let mut factors = Vec::new();
+        for _ in 0..count {
+            if tokens.next()? != "factor" {
+                return None;
+            }
+            let q = BigInt::from_hex(tokens.next()?)?;
+            let exponent = tokens.next()?.parse().ok()?;
+            let witness = BigInt::from_hex(tokens.next()?)?;
+            let certificate = Certificate::read_text(tokens, depth - 1)?;
+            factors.push(FactorProof { q, exponent, witness, certificate });
+        }
+        Some(Certificate::NMinusOne { n, factors })
+    }
+
+    // the step is boxed before recursing, to keep the frame small
+    fn read_curve<'a, I: Iterator<Item = &'a str>>(tokens: &mut I, depth: usize) -> Option<Certificate> {
+        let zero = BigInt::zero();
+        let mut step = Box::new(CurveStep {
+            n: zero, a: zero, b: zero, x: zero, y: zero, m: zero, q: zero,
+            certificate: Certificate::Small(0),
+        });
+        for number in [&mut step.n, &mut step.a, &mut step.b, &mut step.x, &mut step.y, &mut step.m, &mut step.q] {
+            *number = BigInt::from_hex(tokens.next()?)?;
+        }
+        step.certificate = Certificate::read_text(tokens, depth - 1)?;
+        Some(Certificate::EllipticCurve(step))
+    }
+
+}
+
+
+
+fn verify_n_minus_one(n: BigInt, factors: &[FactorProof]) -> bool {
+    let one = BigInt::from(1);
+    if n.is_even() || n <= BigInt::from(3) || n.bits() > 1024 || factors.is_empty() {
+        return false;
+    }
+
+    // f = product of the proven prime powers, it has to divide n - 1
+    let ctx = BarrettCtx::new(n);
+    let n_minus_1 = n - one;
+    let mut f = one;
+    for factor in factors.iter() {
+        let q = factor.q;
+        if factor.exponent == 0 || !verify_certificate(&factor.certificate) || factor.certificate.prime() != q {
+            return false;
+        }
+        for _ in 0..factor.exponent {
+            if f.bits() + q.bits() > n.bits() + 1 {
+                return false;
+            }
+            f *= q;
+        }
+
+        let witness = factor.witness;
+        if witness < BigInt::from(2) || witness >= n_minus_1 {
+            return false;
+        }
+        if ctx.pow_mod(witness, n_minus_1) != one {
+            return false;
+        }
+        let b = ctx.pow_mod(witness, n_minus_1 / q);
+        if b.is_zero() || utils::gcd(b - one, n) != one {
+            return false;
+        }
+    }
+    if f > n_minus_1 || !(n_minus_1 % f).is_zero() {
+        return false;
+    }
+
+    // every prime factor of n is 1 mod f, so f >= sqrt(n) is enough
+    if f >= utils::isqrt(n) {
+        return true;
+    }
+
+    // for n^(1/3) < f < n^(1/2) write n = c2 f^2 + c1 f + 1, then n is prime
+    // unless c1^2 - 4 c2 is a square
+    if f <= utils::iroot(n, 3) {
+        return false;
+    }
+    let m = n_minus_1 / f;
+    let c1 = m % f;
+    let c2 = m / f;
+    let c1_squared = c1.square();
+    let four_c2 = c2 << 2;
+    c1_squared < four_c2 || !utils::is_perfect_square(c1_squared - four_c2)
+}
+
+
+// goldwasser-kilian: with gcd(n, 6) = 1 and q > (n^(1/4) + 1)^2 every prime
+// factor p of n has a point of order q on the curve modulo p, so p > sqrt(n)
+fn verify_curve_step(step: &CurveStep) -> bool {
+    let one = BigInt::from(1);
+    let CurveStep { n, a, b, x, y, m, q, .. } = *step;
+
+    if n.is_even() || n.rem_u64(3) == 0 || n.bits() <= 32 || n.bits() > 1024 {
+        return false;
+    }
+    if a >= n || b >= n || x >= n || y >= n {
+        return false;
+    }
+    if q.is_zero() || !(m % q).is_zero() || q < curve_factor_bound(n) {
+        return false;
+    }
+    if !verify_certificate(&step.certificate) || step.certificate.prime() != q {
+        return false;
+    }
+
+    let mut curve = Curve::new(n, a, b);
+    if !curve.is_nonsingular() || !curve.contains(x, y) {
+        return false;
+    }
+
+    let point = Some(Point { x, y, z: one });
+    let multiple = match curve.multiply(point, m / q) {
+        Some(multiple) => multiple,
+        None => return false,
+    };
+    if utils::gcd(multiple.z, n) != one || curve.multiply(Some(multiple), q).is_some() {
+        return false;
+    }
+    utils::gcd(curve.denominators, n) == one
+}
+
+
+pub fn verify_certificate(certificate: &Certificate) -> bool {
+    match certificate {
+        Certificate::Small(p) => *p < 1 << 32 && crate::small_is_prime(*p),
+        Certificate::NMinusOne { n, factors } => verify_n_minus_one(*n, factors),
+        Certificate::EllipticCurve(step) => verify_curve_step(step),
+    }
+}
+
+
+// n is prime if q is prime, q divides n - 1, q >= sqrt(n) and
+// witness^(n-1) = 1 mod n with gcd(witness^((n-1)/q) - 1, n) = 1 (pocklington)
+#[derive(Clone, Copy)]
+pub struct PocklingtonStep {
+    pub n: BigInt,
+    pub q: BigInt,
+    pub witness: BigInt,
+}
+
+
+// chain of pocklington steps, starting from a prime small enough for trial
+// division. every step uses the prime proven by the step before as q
+#[derive(Clone)]
+pub struct CertificateChain {
+    pub small_prime: u64,
+    pub steps: Vec<PocklingtonStep>,
+}
+
+
+impl CertificateChain {
+
+    pub fn prime(&self) -> BigInt {
+        match self.steps.last() {
+            Some(step) => step.n,
+            None => BigInt::from(self.small_prime as u128),
+        }
+    }
+
+    // the same proof as a general certificate, each step has f = q
+    pub fn to_certificate(&self) -> Certificate {
+        let mut certificate = Certificate::Small(self.small_prime);
+        for step in self.steps.iter() {
+            let factor = FactorProof { q: step.q, exponent: 1, witness: step.witness, certificate };
+            certificate = Certificate::NMinusOne { n: step.n, factors: vec![factor] };
+        }
+        certificate
+    }
+
+}
+
+
+
+pub fn verify_pocklington_step(step: &PocklingtonStep) -> bool {
+    let one = BigInt::from(1);
+    let n = step.n;
+    let q = step.q;
+
+    if n.is_even() || n <= BigInt::from(3) || q.is_zero() {
+        return false;
+    }
+
+    let n_minus_1 = n - one;
+    if !(n_minus_1 % q).is_zero() || q < utils::isqrt(n) {
+        return false;
+    }
+
+    let witness = step.witness;
+    if witness < BigInt::from(2) || witness >= n_minus_1 {
+        return false;
+    }
+    let ctx = BarrettCtx::new(n);
+    if ctx.pow_mod(witness, n_minus_1) != one {
+        return false;
+    }
+
+    let b = ctx.pow_mod(witness, n_minus_1 / q);
+    !b.is_zero() && utils::gcd(b - one, n) == one
+}
+
+
+
+pub fn verify_chain(chain: &CertificateChain) -> bool {
+    let small = chain.small_prime;
+    if small >= 1 << 32 || !crate::small_is_prime(small) {
+        return false;
+    }
+
+    let mut proven = BigInt::from(small as u128);
+    for step in chain.steps.iter() {
+        if step.q != proven || !verify_pocklington_step(step) {
+            return false;
+        }
+        proven = step.n;
+    }
+    true
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos;
+
+    fn round_trip(certificate: &Certificate) {
+        let text = certificate.to_text();
+        let parsed = Certificate::from_text(&text).expect("printed certificate does not parse");
+        assert_eq!(parsed.to_text(), text);
+        assert_eq!(parsed.prime(), certificate.prime());
+        assert!(verify_certificate(&parsed));
+    }
+
+    fn nested(depth: usize, level: &str) -> String {
+        level.repeat(depth) + "small 3\n"
+    }
+
+    #[test]
+    fn pratt_round_trip() {
+        // 3 * 2^k + 1 is prime for these k
+        for k in [36, 41, 66] {
+            let n = (BigInt::from(3) << k) + BigInt::from(1);
+            let certificate = algos::pratt_certificate(n).unwrap();
+            assert!(verify_certificate(&certificate));
+            round_trip(&certificate);
+        }
+    }
+
+    #[test]
+    fn pocklington_round_trip() {
+        // n - 1 = 2^80 k with k a product of two primes above the sieving
+        // bound, so only the power of two is factored
+        let base = BigInt::from(1000003 * 1000033);
+        let mut found = 0;
+        for j in 0..2000u128 {
+            let k = base * BigInt::from(2 * j + 1);
+            let n = (k << 80) + BigInt::from(1);
+            if let Some(certificate) = algos::pocklington_certificate(n) {
+                assert!(verify_certificate(&certificate));
+                round_trip(&certificate);
+                found += 1;
+            }
+        }
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn chain_round_trip() {
+        for bits in [64, 256] {
+            let (prime, chain) = crate::generate_maurer_prime(bits);
+            let certificate = chain.to_certificate();
+            assert_eq!(certificate.prime(), prime);
+            round_trip(&certificate);
+        }
+    }
+
+    #[test]
+    fn malformed_text() {
+        let certificate = Certificate::Small(65537);
+        let text = certificate.to_text();
+        assert!(Certificate::from_text(&text).is_some());
+        assert!(Certificate::from_text(&(text.clone() + "small 3")).is_none());
+        assert!(Certificate::from_text("n-1 7 1\nfactor 3 1 3\n").is_none());
+        assert!(Certificate::from_text("small zz").is_none());
+        assert!(Certificate::from_text("").is_none());
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let levels = [
+            "n-1 7 1\nfactor 3 1 3\n",
+            "curve 10000000001 1 1 1 1 10000000001 10000000001\n",
+        ];
+        for level in levels {
+            let parsed = Certificate::from_text(&nested(MAX_DEPTH - 1, level)).unwrap();
+            assert!(!verify_certificate(&parsed));
+            assert!(Certificate::from_text(&nested(MAX_DEPTH, level)).is_none());
+            assert!(Certificate::from_text(&nested(1_000_000, level)).is_none());
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::algos;
+use crate::parallel_search;
+use crate::BigInt;
+use crate::sieve::{Sieve, sieving_primes};
+
+
+// first kind:  p, 2p + 1, 4p + 3, ...
+// second kind: p, 2p - 1, 4p - 3, ...
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChainKind {
+    First,
+    Second,
+}
+
+
+#[derive(Clone, Copy)]
+pub struct CunninghamChain {
+    pub kind: ChainKind,
+    pub start: BigInt,
+    pub length: usize,
+}
+
+
+impl ChainKind {
+
+    pub fn next(&self, p: BigInt) -> BigInt {
+        match self {
+            ChainKind::First => (p << 1).increase(),
+            ChainKind::Second => (p << 1).decrease(),
+        }
+    }
+
+    // same step as next(), on the residue of p modulo a small prime r
+    fn next_residue(&self, x: u64, r: u64) -> u64 {
+        match self {
+            ChainKind::First => (2 * x + 1) % r,
+            ChainKind::Second => (2 * x + r - 1) % r,
+        }
+    }
+
+}
+
+
+impl CunninghamChain {
+
+    pub fn members(&self) -> Vec<BigInt> {
+        let mut members = vec![self.start];
+        for _ in 1..self.length {
+            members.push(self.kind.next(members[members.len() - 1]));
+        }
+        members
+    }
+
+}
+
+
+// number of consecutive probable primes in the chain starting at p
+pub fn chain_length(kind: ChainKind, p: BigInt) -> usize {
+    let mut length = 0;
+    let mut member = p;
+
+    while member.bits() <= 1024 && is_probable_prime(member) {
+        length += 1;
+        member = kind.next(member);
+    }
+    length
+}
+
+
+// miller-rabin needs n > 3, so 2 and 3 are checked directly
+fn is_probable_prime(n: BigInt) -> bool {
+    if n.bits() <= 2 {
+        return n == BigInt::from(2) || n == BigInt::from(3);
+    }
+    !algos::miller_rabin_test(n, 10).is_composite()
+}
+
+
+fn search_chain(
+    kind: ChainKind, length: usize, bits: usize, stop: &AtomicBool
+) -> Option<CunninghamChain> {
+
+    let primes = sieving_primes(bits);
+    let two = BigInt::from(2);
+
+    // a candidate is dropped if any of the first `length` members of its
+    // chain is divisible by a sieving prime
+    let divides_chain = |r: u64, mut x: u64| {
+        for _ in 0..length {
+            if x == 0 { return true; }
+            x = kind.next_residue(x, r);
+        }
+        false
+    };
+
+    'restart: loop {
+        let mut p = BigInt::random_bits(bits);
+        let mut sieve = Sieve::new(&primes, p, two);
+
+        loop {
+            if stop.load(Ordering::Relaxed) { return None; }
+
+            if !sieve.rejects(divides_chain) {
+                let found = chain_length(kind, p);
+                if found >= length {
+                    return Some(CunninghamChain { kind, start: p, length: found });
+                }
+            }
+
+            p = p.increase_by_2();
+            sieve.advance();
+            if p.bits() > bits { continue 'restart; }
+        }
+    }
+}
+
+
+// finds `count` chains of at least `length` members whose first member
+// has exactly `bits` bits
+pub fn find_cunningham_chains(
+    kind: ChainKind, length: usize, bits: usize, count: usize
+) -> Vec<CunninghamChain> {
+
+    if length == 0 {
+        panic!("Chain length must be at least 1");
+    }
+    if bits < 16 || bits + length > 1025 {
+        panic!("Chain members must be between 16 and 1024 bits");
+    }
+
+    (0..count)
+        .map(|_| parallel_search(move |stop| search_chain(kind, length, bits, stop)))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_chains() {
+        let first = CunninghamChain { kind: ChainKind::First, start: BigInt::from(2), length: 5 };
+        let members: Vec<BigInt> = [2, 5, 11, 23, 47].into_iter().map(BigInt::from).collect();
+        assert_eq!(first.members(), members);
+        assert_eq!(chain_length(ChainKind::First, BigInt::from(2)), 5);
+        assert_eq!(chain_length(ChainKind::First, BigInt::from(89)), 6);
+
+        let second = CunninghamChain { kind: ChainKind::Second, start: BigInt::from(2), length: 3 };
+        let members: Vec<BigInt> = [2, 3, 5].into_iter().map(BigInt::from).collect();
+        assert_eq!(second.members(), members);
+        assert_eq!(chain_length(ChainKind::Second, BigInt::from(2)), 3);
+        assert_eq!(chain_length(ChainKind::Second, BigInt::from(1531)), 5);
+        assert_eq!(chain_length(ChainKind::First, BigInt::from(9)), 0);
+    }
+
+    #[test]
+    fn found_chains_are_prime() {
+        for kind in [ChainKind::First, ChainKind::Second] {
+            for chain in find_cunningham_chains(kind, 3, 24, 2) {
+                assert_eq!(chain.start.bits(), 24);
+                assert!(chain.length >= 3);
+                assert_eq!(chain_length(kind, chain.start), chain.length);
+                for member in chain.members() {
+                    assert!(!algos::miller_rabin_test(member, 20).is_composite());
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1")]
+    fn rejects_empty_chains() {
+        find_cunningham_chains(ChainKind::First, 0, 64, 1);
+    }
+}
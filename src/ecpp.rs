@@ -0,0 +1,487 @@
+use std::sync::OnceLock;
+
+use crate::algos::{self, PrimeResult};
+use crate::barrett::BarrettCtx;
+use crate::bigint::BigInt;
+use crate::certificate::{Certificate, CurveStep};
+use crate::hilbert::CLASS_POLYNOMIALS;
+use crate::utils;
+
+
+fn add_mod(a: BigInt, b: BigInt, n: BigInt) -> BigInt {
+    let sum = a + b;
+    if sum >= n { sum - n } else { sum }
+}
+
+
+fn sub_mod(a: BigInt, b: BigInt, n: BigInt) -> BigInt {
+    if a >= b { a - b } else { a + n - b }
+}
+
+
+
+// (x, y, z) in jacobian coordinates stands for (x / z^2, y / z^3),
+// the point at infinity is None
+#[derive(Clone, Copy)]
+pub struct Point {
+    pub x: BigInt,
+    pub y: BigInt,
+    pub z: BigInt,
+}
+
+
+// y^2 = x^3 + ax + b modulo n, where n does not need to be prime. the z
+// coordinates of all additions are multiplied into `denominators`, as long
+// as that stays coprime to n every result is also correct modulo each prime
+// factor of n
+pub struct Curve {
+    ctx: BarrettCtx,
+    pub a: BigInt,
+    pub b: BigInt,
+    pub denominators: BigInt,
+}
+
+
+impl Curve {
+
+    pub fn new(n: BigInt, a: BigInt, b: BigInt) -> Self {
+        Self { ctx: BarrettCtx::new(n), a, b, denominators: BigInt::from(1) }
+    }
+
+    pub fn contains(&self, x: BigInt, y: BigInt) -> bool {
+        let ctx = &self.ctx;
+        let n = ctx.modulus;
+        let rhs = ctx.mul_mod(add_mod(ctx.square_mod(x), self.a, n), x);
+        ctx.square_mod(y) == add_mod(rhs, self.b, n)
+    }
+
+    // 4a^3 + 27b^2 has to be a unit
+    pub fn is_nonsingular(&self) -> bool {
+        let ctx = &self.ctx;
+        let n = ctx.modulus;
+        let a_cubed = ctx.mul_mod(ctx.square_mod(self.a), self.a);
+        let b_squared = ctx.square_mod(self.b);
+        let discriminant = add_mod(
+            ctx.mul_mod(a_cubed, BigInt::from(4)),
+            ctx.mul_mod(b_squared, BigInt::from(27)),
+            n,
+        );
+        !discriminant.is_zero() && utils::gcd(discriminant, n) == BigInt::from(1)
+    }
+
+    pub fn double(&self, point: Option<Point>) -> Option<Point> {
+        let Point { x, y, z } = point?;
+        let ctx = &self.ctx;
+        let n = ctx.modulus;
+
+        let xx = ctx.square_mod(x);
+        let yy = ctx.square_mod(y);
+        let yyyy = ctx.square_mod(yy);
+        let zz = ctx.square_mod(z);
+
+        // s = 4xy^2, m = 3x^2 + az^4
+        let s = ctx.mul_mod(x, yy);
+        let s = add_mod(s, s, n);
+        let s = add_mod(s, s, n);
+        let m = add_mod(add_mod(xx, xx, n), xx, n);
+        let m = add_mod(m, ctx.mul_mod(self.a, ctx.square_mod(zz)), n);
+
+        let x3 = sub_mod(ctx.square_mod(m), add_mod(s, s, n), n);
+        let eight_yyyy = add_mod(yyyy, yyyy, n);
+        let eight_yyyy = add_mod(eight_yyyy, eight_yyyy, n);
+        let eight_yyyy = add_mod(eight_yyyy, eight_yyyy, n);
+        let y3 = sub_mod(ctx.mul_mod(m, sub_mod(s, x3, n)), eight_yyyy, n);
+        let z3 = ctx.mul_mod(add_mod(y, y, n), z);
+
+        if z3.is_zero() { None } else { Some(Point { x: x3, y: y3, z: z3 }) }
+    }
+
+    pub fn add(&mut self, first: Option<Point>, second: Option<Point>) -> Option<Point> {
+        let (p, q) = match (first, second) {
+            (None, _) => return second,
+            (_, None) => return first,
+            (Some(p), Some(q)) => (p, q),
+        };
+        let ctx = &self.ctx;
+        let n = ctx.modulus;
+
+        let z1z1 = ctx.square_mod(p.z);
+        let z2z2 = ctx.square_mod(q.z);
+        let u1 = ctx.mul_mod(p.x, z2z2);
+        let u2 = ctx.mul_mod(q.x, z1z1);
+        let s1 = ctx.mul_mod(ctx.mul_mod(p.y, q.z), z2z2);
+        let s2 = ctx.mul_mod(ctx.mul_mod(q.y, p.z), z1z1);
+        let z1z2 = ctx.mul_mod(p.z, q.z);
+
+        // same x coordinate, so the points are equal or opposite
+        if u1 == u2 {
+            self.denominators = ctx.mul_mod(self.denominators, z1z2);
+            if s1 == s2 {
+                return self.double(first);
+            }
+            if add_mod(s1, s2, n).is_zero() {
+                return None;
+            }
+            // only possible for composite n
+            self.denominators = BigInt::zero();
+            return None;
+        }
+
+        let h = sub_mod(u2, u1, n);
+        let r = sub_mod(s2, s1, n);
+        let hh = ctx.square_mod(h);
+        let hhh = ctx.mul_mod(h, hh);
+        let v = ctx.mul_mod(u1, hh);
+
+        let x3 = sub_mod(sub_mod(ctx.square_mod(r), hhh, n), add_mod(v, v, n), n);
+        let y3 = sub_mod(ctx.mul_mod(r, sub_mod(v, x3, n)), ctx.mul_mod(s1, hhh), n);
+        let z3 = ctx.mul_mod(z1z2, h);
+
+        self.denominators = ctx.mul_mod(self.denominators, z3);
+        if z3.is_zero() { None } else { Some(Point { x: x3, y: y3, z: z3 }) }
+    }
+
+    pub fn multiply(&mut self, point: Option<Point>, k: BigInt) -> Option<Point> {
+        let mut result = None;
+        for i in (0..k.bits()).rev() {
+            result = self.double(result);
+            if k.bit(i) {
+                result = self.add(result, point);
+            }
+        }
+        result
+    }
+
+}
+
+
+
+// a curve proves n prime with a prime factor q of its order m if
+// q > (n^(1/4) + 1)^2, this bound is slightly larger than that
+pub fn curve_factor_bound(n: BigInt) -> BigInt {
+    (utils::iroot(n, 4) + BigInt::from(2)).square()
+}
+
+
+
+// polynomials modulo n, lowest coefficient first
+fn poly_trim(poly: &mut Vec<BigInt>) {
+    while poly.len() > 1 && poly.last().is_some_and(|c| c.is_zero()) {
+        poly.pop();
+    }
+    if poly.is_empty() {
+        poly.push(BigInt::zero());
+    }
+}
+
+
+// remainder of poly modulo the monic polynomial f
+fn poly_rem(ctx: &BarrettCtx, mut poly: Vec<BigInt>, f: &[BigInt]) -> Vec<BigInt> {
+    let n = ctx.modulus;
+    let degree = f.len() - 1;
+    while poly.len() > degree {
+        let top = poly.pop().unwrap();
+        let shift = poly.len() - degree;
+        for (i, coefficient) in f[..degree].iter().enumerate() {
+            poly[shift + i] = sub_mod(poly[shift + i], ctx.mul_mod(top, *coefficient), n);
+        }
+    }
+    poly_trim(&mut poly);
+    poly
+}
+
+
+fn poly_mul_mod(ctx: &BarrettCtx, a: &[BigInt], b: &[BigInt], f: &[BigInt]) -> Vec<BigInt> {
+    let n = ctx.modulus;
+    let mut product = vec![BigInt::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            product[i + j] = add_mod(product[i + j], ctx.mul_mod(*x, *y), n);
+        }
+    }
+    poly_rem(ctx, product, f)
+}
+
+
+fn poly_pow_mod(ctx: &BarrettCtx, base: &[BigInt], exponent: BigInt, f: &[BigInt]) -> Vec<BigInt> {
+    let mut result = vec![BigInt::from(1)];
+    for i in (0..exponent.bits()).rev() {
+        result = poly_mul_mod(ctx, &result, &result, f);
+        if exponent.bit(i) {
+            result = poly_mul_mod(ctx, &result, base, f);
+        }
+    }
+    result
+}
+
+
+// monic gcd, None if a leading coefficient is not invertible
+fn poly_gcd(ctx: &BarrettCtx, mut a: Vec<BigInt>, mut b: Vec<BigInt>) -> Option<Vec<BigInt>> {
+    let n = ctx.modulus;
+    poly_trim(&mut a);
+    poly_trim(&mut b);
+    while !(b.len() == 1 && b[0].is_zero()) {
+        let inverse = utils::mod_inverse(*b.last().unwrap(), n)?;
+        let monic: Vec<BigInt> = b.iter().map(|c| ctx.mul_mod(*c, inverse)).collect();
+        let rem = poly_rem(ctx, a, &monic);
+        a = monic;
+        b = rem;
+    }
+    Some(a)
+}
+
+
+// a root of the class polynomial of d modulo n, found by splitting it with
+// gcd((x + delta)^((n-1)/2) - 1, h) for random delta
+fn class_polynomial_root(n: BigInt, d: i64) -> Option<BigInt> {
+    let coefficients = CLASS_POLYNOMIALS.iter().find(|(disc, _)| *disc == d)?.1;
+    let mut h: Vec<BigInt> = Vec::new();
+    for text in coefficients.iter() {
+        let value = match text.strip_prefix('-') {
+            Some(digits) => (n - BigInt::from_hex(digits)? % n) % n,
+            None => BigInt::from_hex(text)? % n,
+        };
+        h.push(value);
+    }
+    h.push(BigInt::from(1));
+
+    let ctx = BarrettCtx::new(n);
+    let one = BigInt::from(1);
+    let exponent = (n - one) >> 1;
+    let mut factor = h.clone();
+
+    for _ in 0..64 {
+        if factor.len() == 2 {
+            let root = sub_mod(BigInt::zero(), factor[0], n);
+            let value = h.iter().rev().fold(BigInt::zero(), |acc, c| add_mod(ctx.mul_mod(acc, root), *c, n));
+            return if value.is_zero() { Some(root) } else { None };
+        }
+
+        let delta = BigInt::random() % n;
+        let mut power = poly_pow_mod(&ctx, &[delta, one], exponent, &factor);
+        power[0] = sub_mod(power[0], one, n);
+        let split = poly_gcd(&ctx, power, factor.clone())?;
+        if split.len() > 1 && split.len() < factor.len() {
+            factor = split;
+        }
+    }
+    None
+}
+
+
+// n + 1 - t for the traces t of the curves with complex multiplication by
+// discriminant d, empty unless 4n = u^2 + |d| v^2 (cornacchia)
+fn curve_orders(n: BigInt, d: i64) -> Vec<BigInt> {
+    let one = BigInt::from(1);
+    let abs_d = BigInt::from(d.unsigned_abs() as u128);
+    if utils::jacobi(n - abs_d, n) != 1 {
+        return Vec::new();
+    }
+    let mut b = match utils::sqrt_mod(n - abs_d, n) {
+        Some(root) => root,
+        None => return Vec::new(),
+    };
+    if b.is_even() != (d % 2 == 0) {
+        b = n - b;
+    }
+
+    let four_n = n << 2;
+    let limit = utils::isqrt(four_n);
+    let mut a = n << 1;
+    while b > limit {
+        (a, b) = (b, a % b);
+    }
+
+    let rest = four_n - b.square();
+    if !(rest % abs_d).is_zero() {
+        return Vec::new();
+    }
+    let c = rest / abs_d;
+    let v = utils::isqrt(c);
+    if v.square() != c {
+        return Vec::new();
+    }
+
+    let u = b;
+    let traces = match d {
+        -4 => vec![u, v << 1],
+        -3 => {
+            let three_v = v * BigInt::from(3);
+            let difference = if u >= three_v { u - three_v } else { three_v - u };
+            vec![u, (u + three_v) >> 1, difference >> 1]
+        }
+        _ => vec![u],
+    };
+
+    let mut orders = Vec::new();
+    for t in traces {
+        orders.push(n + one + t);
+        if t <= n {
+            orders.push(n + one - t);
+        }
+    }
+    orders
+}
+
+
+// discriminant, curve order and its large probable prime factor
+struct Candidate {
+    d: i64,
+    m: BigInt,
+    q: BigInt,
+}
+
+
+// odd primes below 2^21 in groups whose product fits in a u64, so a curve
+// order needs one division per group to find its small factors
+fn order_prime_groups() -> &'static [(u64, Vec<u64>)] {
+    static GROUPS: OnceLock<Vec<(u64, Vec<u64>)>> = OnceLock::new();
+
+    GROUPS.get_or_init(|| {
+        const LIMIT: usize = 1 << 21;
+        let mut composite = vec![false; LIMIT];
+        let mut groups: Vec<(u64, Vec<u64>)> = Vec::new();
+
+        for p in (3..LIMIT).step_by(2) {
+            if composite[p] { continue; }
+            for multiple in (p * p..LIMIT).step_by(2 * p) {
+                composite[multiple] = true;
+            }
+
+            let p = p as u64;
+            match groups.last_mut() {
+                Some((product, group)) if product.checked_mul(p).is_some() => {
+                    *product *= p;
+                    group.push(p);
+                }
+                _ => groups.push((p, vec![p])),
+            }
+        }
+        groups
+    })
+}
+
+
+// all curve orders of n whose cofactor after removing small primes could
+// prove n prime, smallest cofactor first. removing the primes below 2^21
+// instead of only the sieving primes gives about a third more candidates,
+// which makes a dead end in the search several times less likely
+fn candidates(n: BigInt) -> Vec<Candidate> {
+    let bound = curve_factor_bound(n);
+    let mut found = Vec::new();
+
+    for (d, _) in CLASS_POLYNOMIALS.iter() {
+        for m in curve_orders(n, *d) {
+            let mut q = m >> m.trailing_zeros();
+            for (product, group) in order_prime_groups() {
+                let rem = q.rem_u64(*product);
+                for prime in group.iter().filter(|prime| rem % *prime == 0) {
+                    while q.rem_u64(*prime) == 0 {
+                        q /= BigInt::from(*prime as u128);
+                    }
+                }
+            }
+
+            if q >= bound && q < n && algos::miller_rabin_test(q, 1) == PrimeResult::ProbablePrime {
+                found.push(Candidate { d: *d, m, q });
+            }
+        }
+    }
+    found.sort_by(|a, b| a.q.partial_cmp(&b.q).unwrap());
+    found
+}
+
+
+// curve with m points for the discriminant d and a point on it whose
+// multiple by m / q has order q, as (a, b, x, y)
+fn find_curve(n: BigInt, d: i64, m: BigInt, q: BigInt) -> Option<(BigInt, BigInt, BigInt, BigInt)> {
+    let zero = BigInt::zero();
+    let ctx = BarrettCtx::new(n);
+
+    // curves with the j-invariant of d and their quadratic twists
+    let (a0, b0) = if d == -3 || d == -4 {
+        (zero, zero)
+    } else {
+        let j = class_polynomial_root(n, d)?;
+        let denominator = sub_mod(BigInt::from(1728), j, n);
+        if j.is_zero() || denominator.is_zero() {
+            return None;
+        }
+        let k = ctx.mul_mod(j, utils::mod_inverse(denominator, n)?);
+        (ctx.mul_mod(k, BigInt::from(3)), add_mod(k, k, n))
+    };
+
+    for attempt in 0..100 {
+        let (a, b) = match d {
+            -3 => (zero, BigInt::random() % n),
+            -4 => (BigInt::random() % n, zero),
+            _ if attempt % 2 == 0 => (a0, b0),
+            _ => {
+                let mut c = BigInt::random() % n;
+                while utils::jacobi(c, n) != -1 {
+                    c = BigInt::random() % n;
+                }
+                let c_squared = ctx.square_mod(c);
+                (ctx.mul_mod(a0, c_squared), ctx.mul_mod(b0, ctx.mul_mod(c_squared, c)))
+            }
+        };
+
+        let mut curve = Curve::new(n, a, b);
+        if !curve.is_nonsingular() {
+            continue;
+        }
+
+        let (x, y) = loop {
+            let x = BigInt::random() % n;
+            let rhs = add_mod(ctx.mul_mod(add_mod(ctx.square_mod(x), a, n), x), b, n);
+            if let Some(y) = utils::sqrt_mod(rhs, n) {
+                break (x, y);
+            }
+        };
+
+        let point = Some(Point { x, y, z: BigInt::from(1) });
+        let multiple = curve.multiply(point, m / q);
+        if multiple.is_some() && curve.multiply(multiple, q).is_none() {
+            return Some((a, b, x, y));
+        }
+    }
+    None
+}
+
+
+// depth first search down the candidate cofactors, until one is small
+// enough for an n - 1 certificate
+fn prove(n: BigInt) -> Option<Certificate> {
+    if n.bits() <= 128 {
+        if let Some(certificate) = algos::pocklington_certificate(n) {
+            return Some(certificate);
+        }
+        if n.bits() <= 32 {
+            return None;
+        }
+    }
+
+    for Candidate { d, m, q } in candidates(n) {
+        let Some(certificate) = prove(q) else { continue };
+        if let Some((a, b, x, y)) = find_curve(n, d, m, q) {
+            return Some(Certificate::EllipticCurve(Box::new(CurveStep { n, a, b, x, y, m, q, certificate })));
+        }
+    }
+    None
+}
+
+
+// elliptic curve primality proof (atkin-morain) using the discriminants
+// of the class polynomial table. None if n is composite or no chain of
+// curves was found. with only small class numbers and trial division to
+// split the curve orders this happens for some primes above 256 bits,
+// generate_proven_prime then tries an n - 1 proof instead
+pub fn ecpp_certificate(n: BigInt) -> Option<Certificate> {
+    if n.bits() > 1024 {
+        return None;
+    }
+    if n.bits() > 32 && (n.is_even() || algos::miller_rabin_test(n, 10).is_composite()) {
+        return None;
+    }
+    prove(n)
+}
@@ -0,0 +1,659 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{parallel_search, rng, utils};
+use crate::bigint::{BigInt, N};
+use crate::barrett::BarrettCtx;
+use crate::primality::{Bpsw, Integer, PrimalityTest, add_mod, sub_mod};
+use crate::sieve::{PrimeIter, sieving_primes};
+
+
+// differences multiplied together before each gcd
+const BATCH: usize = 128;
+
+// giant step of the second stages, 2 * 3 * 5 * 7 * 11
+const WHEEL: u64 = 2310;
+
+
+
+// one run of brent's variant of pollard's rho with f(x) = x^2 + c. None if
+// the cycle closed without separating a factor
+fn brent_run<T: Integer>(n: T, ctx: &T::Ctx, c: T, start: T) -> Option<T> {
+    let one = T::from_u64(1);
+    let f = |x: T| add_mod(T::square_mod(ctx, x), c, n);
+    let distance = |a: T, b: T| if a > b { a - b } else { b - a };
+
+    let mut x = start;
+    let mut y = start;
+    let mut saved = start;
+    let mut product = one;
+    let mut g = one;
+    let mut r = 1;
+
+    // y runs ahead of x by r..2r steps, the differences x - y are only
+    // checked once per batch
+    while g == one {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+        let mut k = 0;
+        while k < r && g == one {
+            saved = y;
+            for _ in 0..BATCH.min(r - k) {
+                y = f(y);
+                product = T::mul_mod(ctx, product, distance(x, y));
+            }
+            g = product.gcd(n);
+            k += BATCH;
+        }
+        r *= 2;
+    }
+
+    // the batch overshot, redo it one step at a time
+    if g == n {
+        loop {
+            saved = f(saved);
+            g = distance(x, saved).gcd(n);
+            if g != one {
+                break;
+            }
+        }
+    }
+    if g == n { None } else { Some(g) }
+}
+
+
+
+// a nontrivial factor of the composite n, the expected number of steps is
+// about the square root of its smallest prime factor
+pub fn pollard_brent<T: Integer>(n: T) -> T {
+    let two = T::from_u64(2);
+    if n > two && !n.bit(0) {
+        return two;
+    }
+    // barrett reduction is limited to 1024 bit moduli
+    if n.bits() > 1024 {
+        panic!("Number must be at most 1024 bits");
+    }
+    if !Bpsw.test(n).is_composite() {
+        panic!("Number must be composite");
+    }
+
+    let ctx = n.ctx();
+    loop {
+        // c = 0 and c = -2 give degenerate sequences
+        let c = (n - T::from_u64(3)).random_below() + T::from_u64(1);
+        if let Some(factor) = brent_run(n, &ctx, c, n.random_below()) {
+            return factor;
+        }
+    }
+}
+
+
+
+// prime factorization of n > 0 as (prime, exponent) in increasing order.
+// the sieving primes are divided out first and rho splits the rest. prime
+// factors above 2^64 are only probable primes
+pub fn factor<T: Integer>(mut n: T) -> Vec<(T, u32)> {
+    let one = T::from_u64(1);
+    if n < one {
+        panic!("Cannot factor zero");
+    }
+    if n.bits() > 1024 {
+        panic!("Number must be at most 1024 bits");
+    }
+
+    let mut primes = Vec::new();
+    let mut proven = false;
+    for p in std::iter::once(2).chain(sieving_primes(64 * N)) {
+        if T::from_u64(p * p) > n {
+            proven = true;
+            break;
+        }
+        while n.rem_u64(p) == 0 {
+            primes.push(T::from_u64(p));
+            n = n / T::from_u64(p);
+        }
+    }
+
+    // without a factor below the square root, n is prime
+    let mut remaining = if n == one { vec![] } else { vec![n] };
+    while let Some(m) = remaining.pop() {
+        if proven || !Bpsw.test(m).is_composite() {
+            primes.push(m);
+        } else if m.is_perfect_square() {
+            // rho would need about sqrt(p) steps to split p^2
+            let s = m.isqrt();
+            remaining.push(s);
+            remaining.push(s);
+        } else {
+            let d = pollard_brent(m);
+            remaining.push(d);
+            remaining.push(m / d);
+        }
+    }
+
+    primes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut factors: Vec<(T, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+
+
+// (V_k, V_(k+1)) of the lucas sequence V_0 = 2, V_1 = v, V_(i+1) = v V_i - V_(i-1),
+// using V_2i = V_i^2 - 2 and V_(2i+1) = V_i V_(i+1) - v
+fn lucas_v(ctx: &BarrettCtx, v: BigInt, k: u64) -> (BigInt, BigInt) {
+    let n = ctx.modulus;
+    let two = BigInt::from(2);
+    let (mut x, mut y) = (two, v);
+    for i in (0..64 - k.leading_zeros()).rev() {
+        let mixed = sub_mod(ctx.mul_mod(x, y), v, n);
+        if (k >> i) & 1 == 1 {
+            (x, y) = (mixed, sub_mod(ctx.square_mod(y), two, n));
+        } else {
+            (x, y) = (sub_mod(ctx.square_mod(x), two, n), mixed);
+        }
+    }
+    (x, y)
+}
+
+
+
+// raises x to every prime power below b1 with power(x, q) and returns
+// x together with gcd(x - target, n). the gcd is taken once per batch of
+// primes, if a batch catches all factors at once it is redone one prime
+// at a time
+fn stage1<F>(n: BigInt, mut x: BigInt, target: BigInt, b1: u64, power: F) -> (BigInt, BigInt)
+where F: Fn(BigInt, u64) -> BigInt
+{
+    let one = BigInt::from(1);
+    let powers: Vec<u64> = PrimeIter::new(2)
+        .take_while(|p| *p <= b1)
+        .map(|p| {
+            let mut q = p;
+            while q <= b1 / p { q *= p; }
+            q
+        })
+        .collect();
+
+    for batch in powers.chunks(BATCH) {
+        let saved = x;
+        for q in batch {
+            x = power(x, *q);
+        }
+        let g = utils::gcd(sub_mod(x, target, n), n);
+        if g == one {
+            continue;
+        }
+        if g != n {
+            return (x, g);
+        }
+        x = saved;
+        for q in batch {
+            x = power(x, *q);
+            let g = utils::gcd(sub_mod(x, target, n), n);
+            if g != one {
+                return (x, g);
+            }
+        }
+    }
+    (x, one)
+}
+
+
+
+// gcd of n with the product of V_q(v) - 2 over the primes b1 < q <= b2.
+// with q = m * WHEEL +- j, V_q(v) = 2 mod p exactly when V_(m WHEEL) = V_j
+// mod p, so one giant step V_(m WHEEL) serves every prime near m * WHEEL
+fn stage2(ctx: &BarrettCtx, v: BigInt, b1: u64, b2: u64) -> BigInt {
+    let n = ctx.modulus;
+    let half = WHEEL / 2;
+
+    // V_j for odd j <= WHEEL / 2, V_(j+2) = V_j V_2 - V_(j-2) and V_-1 = V_1
+    let v2 = sub_mod(ctx.square_mod(v), BigInt::from(2), n);
+    let mut baby = vec![v, sub_mod(ctx.mul_mod(v, v2), v, n)];
+    while baby.len() < half.div_ceil(2) as usize {
+        let i = baby.len();
+        baby.push(sub_mod(ctx.mul_mod(baby[i - 1], v2), baby[i - 2], n));
+    }
+
+    let step = lucas_v(ctx, v, WHEEL).0;
+    let mut m = (b1 + 1 + half) / WHEEL;
+    let (mut giant, mut next) = lucas_v(ctx, step, m);
+    let mut product = BigInt::from(1);
+    let mut last = (0, 0);
+
+    for q in PrimeIter::new(b1 + 1).take_while(|q| *q <= b2) {
+        while m < (q + half) / WHEEL {
+            (giant, next) = (next, sub_mod(ctx.mul_mod(next, step), giant, n));
+            m += 1;
+        }
+        // m * WHEEL - j and m * WHEEL + j share the same difference
+        let j = q.abs_diff(m * WHEEL);
+        if (m, j) != last {
+            product = ctx.mul_mod(product, sub_mod(giant, baby[(j / 2) as usize], n));
+            last = (m, j);
+        }
+    }
+    utils::gcd(product, n)
+}
+
+
+
+fn check_bounds(n: BigInt, b1: u64, b2: u64) {
+    if n.bits() < 2 || !n.bit(0) {
+        panic!("Number must be odd and greater than 1");
+    }
+    if n.bits() > 1024 {
+        panic!("Number must be at most 1024 bits");
+    }
+    if b1 < 2 || b2 < b1 {
+        panic!("Bounds must satisfy 2 <= B1 <= B2");
+    }
+}
+
+
+
+// pollard's p - 1, finds a prime factor p of n when p - 1 is b1-smooth
+// apart from at most one prime below b2. n is odd with at most 1024 bits
+pub fn pollard_pm1(n: BigInt, b1: u64, b2: u64) -> Option<BigInt> {
+    check_bounds(n, b1, b2);
+    let one = BigInt::from(1);
+    let ctx = BarrettCtx::new(n);
+
+    let power = |x, q| ctx.pow_mod(x, BigInt::from(q as u128));
+    let (x, g) = stage1(n, BigInt::from(3), one, b1, power);
+    if g == n {
+        return None;
+    }
+    if g != one {
+        return Some(g);
+    }
+
+    // x^k = 1 and x^k + x^-k = 2 hold for the same p
+    // x = 0 when every prime factor of n is 3, that separates nothing
+    let inverse = match utils::mod_inverse(x, n) {
+        Some(inverse) => inverse,
+        None => return Some(utils::gcd(x, n)).filter(|g| *g != n),
+    };
+    let g = stage2(&ctx, add_mod(x, inverse, n), b1, b2);
+    if g == one || g == n { None } else { Some(g) }
+}
+
+
+
+// williams' p + 1, finds a prime factor p of n when p + 1 is b1-smooth
+// apart from at most one prime below b2. this only works if the seed's
+// discriminant is a non-residue mod p, otherwise it behaves like p - 1.
+// n is odd with at most 1024 bits
+pub fn williams_pp1(n: BigInt, b1: u64, b2: u64) -> Option<BigInt> {
+    check_bounds(n, b1, b2);
+    let one = BigInt::from(1);
+    let two = BigInt::from(2);
+    let ctx = BarrettCtx::new(n);
+
+    // discriminants 5, 3 and 2 times a square
+    for seed in [3, 4, 6] {
+        let power = |v, q| lucas_v(&ctx, v, q).0;
+        let (v, g) = stage1(n, BigInt::from(seed), two, b1, power);
+        if g == n {
+            continue;
+        }
+        if g != one {
+            return Some(g);
+        }
+        let g = stage2(&ctx, v, b1, b2);
+        if g != one && g != n {
+            return Some(g);
+        }
+    }
+    None
+}
+
+
+
+// projective point (x : z) on a montgomery curve, y is never needed
+#[derive(Clone, Copy)]
+struct Point {
+    x: BigInt,
+    z: BigInt,
+}
+
+
+// b y^2 = x^3 + a x^2 + x modulo n, storing (a + 2) / 4
+struct Curve {
+    ctx: BarrettCtx,
+    a24: BigInt,
+}
+
+
+impl Curve {
+
+    // suyama's parametrisation, the group order is divisible by 12. Err
+    // holds gcd(n, denominator) if the setup cannot invert it
+    #[allow(clippy::result_large_err)]
+    fn suyama(ctx: BarrettCtx, sigma: u64) -> Result<(Self, Point), BigInt> {
+        let n = ctx.modulus;
+        let sigma = BigInt::from(sigma as u128) % n;
+        let u = sub_mod(ctx.square_mod(sigma), BigInt::from(5), n);
+        let v = ctx.mul_mod(sigma, BigInt::from(4));
+        let cube = |a| ctx.mul_mod(ctx.square_mod(a), a);
+
+        // a24 = (v - u)^3 (3u + v) / (16 u^3 v)
+        let numerator = ctx.mul_mod(
+            cube(sub_mod(v, u, n)),
+            add_mod(ctx.mul_mod(u, BigInt::from(3)), v, n),
+        );
+        let denominator = ctx.mul_mod(ctx.mul_mod(cube(u), v), BigInt::from(16));
+        let inverse = utils::mod_inverse(denominator, n)
+            .ok_or_else(|| utils::gcd(denominator, n))?;
+
+        let curve = Self { ctx, a24: ctx.mul_mod(numerator, inverse) };
+        Ok((curve, Point { x: cube(u), z: cube(v) }))
+    }
+
+    fn double(&self, p: Point) -> Point {
+        let (ctx, n) = (&self.ctx, self.ctx.modulus);
+        let sum = ctx.square_mod(add_mod(p.x, p.z, n));
+        let difference = ctx.square_mod(sub_mod(p.x, p.z, n));
+        let product = sub_mod(sum, difference, n);
+        Point {
+            x: ctx.mul_mod(sum, difference),
+            z: ctx.mul_mod(product, add_mod(difference, ctx.mul_mod(self.a24, product), n)),
+        }
+    }
+
+    // p + q given p - q
+    fn add(&self, p: Point, q: Point, difference: Point) -> Point {
+        let (ctx, n) = (&self.ctx, self.ctx.modulus);
+        let u = ctx.mul_mod(sub_mod(p.x, p.z, n), add_mod(q.x, q.z, n));
+        let v = ctx.mul_mod(add_mod(p.x, p.z, n), sub_mod(q.x, q.z, n));
+        Point {
+            x: ctx.mul_mod(difference.z, ctx.square_mod(add_mod(u, v, n))),
+            z: ctx.mul_mod(difference.x, ctx.square_mod(sub_mod(u, v, n))),
+        }
+    }
+
+    // (k p, (k + 1) p) with the montgomery ladder
+    fn mul(&self, p: Point, k: u64) -> (Point, Point) {
+        let infinity = Point { x: BigInt::from(1), z: BigInt::zero() };
+        let (mut r0, mut r1) = (infinity, p);
+        for i in (0..64 - k.leading_zeros()).rev() {
+            if (k >> i) & 1 == 1 {
+                (r0, r1) = (self.add(r1, r0, p), self.double(r1));
+            } else {
+                (r0, r1) = (self.double(r0), self.add(r1, r0, p));
+            }
+        }
+        (r0, r1)
+    }
+
+}
+
+
+
+// one curve with stage 1 up to b1 and the same baby-step giant-step stage 2
+// as p + 1, comparing x(m WHEEL q) with x(j q). Some(n) if every factor of n
+// was found at once
+fn ecm_curve(n: BigInt, b1: u64, b2: u64, stop: &AtomicBool) -> Option<BigInt> {
+    let one = BigInt::from(1);
+    let ctx = BarrettCtx::new(n);
+    let sigma = rng::u64() % (u64::MAX - 6) + 6;
+    let (curve, mut q) = match Curve::suyama(ctx, sigma) {
+        Ok(setup) => setup,
+        Err(g) => return Some(g),
+    };
+
+    for p in PrimeIter::new(2).take_while(|p| *p <= b1) {
+        if stop.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut power = p;
+        while power <= b1 / p { power *= p; }
+        q = curve.mul(q, power).0;
+    }
+    let g = utils::gcd(q.z, n);
+    if g != one || stop.load(Ordering::Relaxed) {
+        return Some(g).filter(|g| *g != one);
+    }
+
+    // j q for odd j <= WHEEL / 2
+    let half = WHEEL / 2;
+    let doubled = curve.double(q);
+    let mut baby = vec![q, curve.add(doubled, q, q)];
+    while baby.len() < half.div_ceil(2) as usize {
+        let i = baby.len();
+        baby.push(curve.add(baby[i - 1], doubled, baby[i - 2]));
+    }
+
+    let step = curve.mul(q, WHEEL).0;
+    let mut m = (b1 + 1 + half) / WHEEL;
+    let (mut giant, mut next) = curve.mul(step, m);
+    let mut product = one;
+    let mut last = (0, 0);
+
+    for prime in PrimeIter::new(b1 + 1).take_while(|prime| *prime <= b2) {
+        while m < (prime + half) / WHEEL {
+            (giant, next) = (next, curve.add(next, step, giant));
+            m += 1;
+        }
+        let j = prime.abs_diff(m * WHEEL);
+        if (m, j) != last {
+            let b = baby[(j / 2) as usize];
+            let cross = sub_mod(ctx.mul_mod(giant.x, b.z), ctx.mul_mod(b.x, giant.z), n);
+            product = ctx.mul_mod(product, cross);
+            last = (m, j);
+        }
+    }
+    Some(utils::gcd(product, n)).filter(|g| *g != one)
+}
+
+
+
+// lenstra's elliptic curve method on up to `curves` random curves, shared
+// between the search threads. finds a prime factor p of n when one of the
+// curve orders mod p is b1-smooth apart from at most one prime below b2.
+// n is odd with at most 1024 bits
+pub fn ecm(n: BigInt, b1: u64, b2: u64, curves: usize) -> Option<BigInt> {
+    if n.bits() < 2 || !n.bit(0) {
+        panic!("Number must be odd and greater than 1");
+    }
+    if n.bits() > 1024 {
+        panic!("Number must be at most 1024 bits");
+    }
+    // stage 2 needs every prime above b1 to be at least one giant step
+    if b1 < WHEEL / 2 || b2 < b1 {
+        panic!("Bounds must satisfy {} <= B1 <= B2", WHEEL / 2);
+    }
+    if curves == 0 {
+        return None;
+    }
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let finished = Arc::new(AtomicUsize::new(0));
+
+    // the thread finishing the last curve reports the failure, so the
+    // search always ends with a result
+    parallel_search(move |stop| {
+        while started.fetch_add(1, Ordering::Relaxed) < curves {
+            if stop.load(Ordering::Relaxed) {
+                return None;
+            }
+            match ecm_curve(n, b1, b2, stop) {
+                Some(g) if g != n => return Some(Some(g)),
+                _ => {}
+            }
+            if finished.fetch_add(1, Ordering::Relaxed) + 1 == curves {
+                return Some(None);
+            }
+        }
+        None
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(factors: &[(BigInt, u32)]) -> BigInt {
+        let mut product = BigInt::from(1);
+        for (p, e) in factors.iter() {
+            for _ in 0..*e {
+                product *= *p;
+            }
+        }
+        product
+    }
+
+    #[test]
+    fn pollard_brent_splits_semiprimes() {
+        let p = 4294967291u64;
+        let q = 4294967279u64;
+        let d = pollard_brent(p as u128 * q as u128);
+        assert!(d == p as u128 || d == q as u128);
+
+        let d = pollard_brent(1000003u64 * 998244353);
+        assert!(d == 1000003 || d == 998244353);
+
+        // rho finds the small factor of a 550 bit number
+        let one = BigInt::from(1);
+        let m521 = (one << 521) - one;
+        let p = BigInt::from(p as u128);
+        assert_eq!(pollard_brent(p * m521), p);
+        assert_eq!(pollard_brent(BigInt::from(1 << 20)), BigInt::from(2));
+    }
+
+    #[test]
+    fn factor_products_of_known_primes() {
+        let expected: Vec<(u128, u32)> = vec![
+            (2, 3), (3, 1), (65537, 2), ((1 << 31) - 1, 1), (4294967291, 1),
+        ];
+        let mut n = 1u128;
+        for (p, e) in expected.iter() {
+            n *= p.pow(*e);
+        }
+        assert_eq!(factor(n), expected);
+        assert_eq!(factor(1u128), vec![]);
+        assert_eq!(factor(65537u64 * 65537 * 65537), vec![(65537, 3)]);
+
+        // two 32 bit primes next to a large prime, and a 64 bit prime squared
+        let one = BigInt::from(1);
+        let p64 = BigInt::from(18446744073709551557);
+        let products = [
+            vec![
+                (BigInt::from(5), 2),
+                (BigInt::from(4294967279), 1),
+                (BigInt::from(4294967291), 1),
+                ((one << 127) - one, 1),
+            ],
+            vec![(BigInt::from(3), 1), (p64, 2)],
+        ];
+        for factors in products {
+            assert_eq!(factor(expand(&factors)), factors);
+        }
+    }
+
+    // largest prime factor of n
+    fn largest_factor(n: u64) -> u64 {
+        factor(n).last().unwrap().0
+    }
+
+    // p * (2^127 - 1), where 2^127 - 2 and 2^127 are far from smooth
+    fn with_large_prime(p: u64) -> BigInt {
+        let one = BigInt::from(1);
+        BigInt::from(p as u128) * ((one << 127) - one)
+    }
+
+    #[test]
+    fn pollard_pm1_stages() {
+        // every prime power in p - 1 = 210k is below 1000, stage 1 finds p
+        let p = (50..100).map(|k| 210 * k + 1).find(|p| crate::small_is_prime(*p)).unwrap();
+        let n = with_large_prime(p);
+        assert_eq!(pollard_pm1(n, 1000, 1000), Some(BigInt::from(p as u128)));
+
+        // p - 1 = 2k * 9973 needs stage 2
+        let p = (1..500).map(|k| 2 * k * 9973 + 1).find(|p| crate::small_is_prime(*p)).unwrap();
+        let n = with_large_prime(p);
+        assert_eq!(pollard_pm1(n, 1000, 1000), None);
+        assert_eq!(pollard_pm1(n, 1000, 10000), Some(BigInt::from(p as u128)));
+
+        // the seed 3 vanishes modulo powers of 3, this must not return n
+        for n in [9, 27, 81, 243, 3 * 3 * 5 * 5] {
+            let n = BigInt::from(n);
+            if let Some(g) = pollard_pm1(n, 1000, 10000) {
+                assert!(g > BigInt::from(1) && g < n && (n % g).is_zero());
+            }
+        }
+    }
+
+    #[test]
+    fn williams_pp1_stages() {
+        // p + 1 = 2k * 9973 with p - 1 not smooth, and a seed discriminant
+        // 5, 3 or 2 that is a non-residue mod p
+        let mut tested = 0;
+        for k in 1..500 {
+            let p = 2 * k * 9973 - 1;
+            if !crate::small_is_prime(p) || largest_factor(p - 1) <= 10000 {
+                continue;
+            }
+            if [5, 3, 2].iter().all(|d| utils::jacobi_u64(*d, p) == 1) {
+                continue;
+            }
+            let n = with_large_prime(p);
+            assert_eq!(williams_pp1(n, 1000, 10000), Some(BigInt::from(p as u128)), "{}", p);
+            assert_eq!(pollard_pm1(n, 1000, 10000), None);
+            tested += 1;
+        }
+        assert!(tested > 0);
+    }
+
+    #[test]
+    fn ecm_finds_small_factor() {
+        let n = with_large_prime(4294967291);
+        assert_eq!(ecm(n, 2000, 100000, 500), Some(BigInt::from(4294967291)));
+        assert_eq!(ecm(n, 2000, 100000, 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 1024 bits")]
+    fn pollard_pm1_rejects_wide_numbers() {
+        pollard_pm1((BigInt::from(1) << 1100).increase(), 1000, 10000);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 1024 bits")]
+    fn williams_pp1_rejects_wide_numbers() {
+        williams_pp1((BigInt::from(1) << 1100).increase(), 1000, 10000);
+    }
+
+    // with the usual bounds for 20 digit factors this takes tens of curves
+    // at about 4 seconds each in a debug build, so it only runs on request
+    // with cargo test --release -- --ignored
+    #[test]
+    #[ignore]
+    fn ecm_finds_twenty_digit_factor() {
+        let p = 10000000000000000051;
+        let n = with_large_prime(p);
+        assert_eq!(ecm(n, 11000, 1900000, 1000), Some(BigInt::from(p as u128)));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 1024 bits")]
+    fn ecm_rejects_wide_numbers() {
+        ecm((BigInt::from(1) << 1100).increase(), 2000, 100000, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 1024 bits")]
+    fn factor_rejects_wide_numbers() {
+        factor(BigInt::from(3) << 1100);
+    }
+}
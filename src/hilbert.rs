@@ -0,0 +1,912 @@
+// hilbert class polynomials of the fundamental discriminants d with
+// |d| < 1600 and class number at most 6. coefficients are hexadecimal,
+// from the constant term up to the second highest one, the polynomials
+// are monic
+pub const CLASS_POLYNOMIALS: &[(i64, &[&str])] = &[
+    (-3, &["0"]),
+    (-4, &["-6c0"]),
+    (-7, &["d2f"]),
+    (-8, &["-1f40"]),
+    (-11, &["8000"]),
+    (-15, &[
+        "-73ab2cf",
+        "2ea31",
+    ]),
+    (-19, &["d8000"]),
+    (-20, &[
+        "-289e7000",
+        "-134980",
+    ]),
+    (-23, &[
+        "b9daf6bf6ef",
+        "-1330a8d6b",
+        "3547a6",
+    ]),
+    (-24, &[
+        "36a689000",
+        "-49c680",
+    ]),
+    (-31, &[
+        "5904b6390e4df",
+        "-da9c1ff36",
+        "25a96eb",
+    ]),
+    (-35, &[
+        "-1f40000000",
+        "7080000",
+    ]),
+    (-39, &[
+        "1224f9696ab704c91",
+        "1865960012d5913",
+        "-6416c86332",
+        "13c2c54c",
+    ]),
+    (-40, &[
+        "8477dc09000",
+        "-195f8e80",
+    ]),
+    (-43, &["34bc0000"]),
+    (-47, &[
+        "d4538f4d02b7d79c06e9f",
+        "-32c33943d490d9665e2",
+        "46fcb49a1162f955",
+        "-9158109379d",
+        "8693d08d",
+    ]),
+    (-51, &[
+        "5b200000000",
+        "14a468000",
+    ]),
+    (-52, &[
+        "-20449785e7000",
+        "-19b160980",
+    ]),
+    (-55, &[
+        "-3ef1d63bbdce8b8afcf",
+        "95afbbb5e27804341",
+        "-130d6dd9809f",
+        "30f01e651",
+    ]),
+    (-56, &[
+        "2219340277f51000000",
+        "1f55336f7ab00000",
+        "7513cfb8b6000",
+        "-3c6cf6d00",
+    ]),
+    (-59, &[
+        "533000000000000",
+        "-801140000000",
+        "707ec0000",
+    ]),
+    (-67, &["2245ae8000"]),
+    (-68, &[
+        "-1ba6d0b75bae10f000000",
+        "-11442c503177300000",
+        "-10d736fa9a6a000",
+        "-297e34ab00",
+    ]),
+    (-79, &[
+        "44e3e1b929e8fa9f7b2282435f",
+        "-12eecd1585a98d3038dc60fe",
+        "17bc6a787ce122173918f",
+        "-169e7f27a74f2c",
+        "137cdf60be8",
+    ]),
+    (-83, &[
+        "1dcd65000000000000",
+        "-9366fc40000000",
+        "272c2320000",
+    ]),
+    (-84, &[
+        "-43eff6b51676d8f000000",
+        "12cf0269ba4c8e500000",
+        "-4e996e3ef5c2a000",
+        "-2e8502f5300",
+    ]),
+    (-87, &[
+        "69e38cc58a74093ec8d6dc48d13bb1",
+        "154ee4c79e0b3e8c9223a5210e69",
+        "647c2a79ae47e2eb7672b8b55",
+        "176d3965896c12cfe843d8",
+        "1300f4fcf535a3e",
+        "4d711b76703",
+    ]),
+    (-88, &[
+        "db3e409b51289000",
+        "-5b9a1dc0680",
+    ]),
+    (-91, &[
+        "-da9a240000000",
+        "96be8de0000",
+    ]),
+    (-103, &[
+        "58d4301f3117be5382190132b202f",
+        "a8920999b9e85fde08a8d3c911",
+        "ff71aa4f454def856f24e90",
+        "12fab514f96906b",
+        "3fee32c111bd",
+    ]),
+    (-104, &[
+        "c9a4d534d14723f492d9000000000",
+        "-144d265daf747f92d8380000000",
+        "1165a81ce7931bf3afbf000000",
+        "19a7610e4007743e700000",
+        "28174055a214387000",
+        "-4a9aafa3eb80",
+    ]),
+    (-107, &[
+        "477e59eaa00000000000",
+        "-5de069f940000000",
+        "760986478000",
+    ]),
+    (-115, &[
+        "1ceacb3c0000000",
+        "18523fe2a0000",
+    ]),
+    (-116, &[
+        "-4bc7f406a67757b586628f7000000000",
+        "1b9d07fde9421a2005a22b80000000",
+        "-347b2a55b06b22a6849a1000000",
+        "-2b2c7c8476df7e12a700000",
+        "-2576495f5b7a48c9000",
+        "-1c2625ebe5c80",
+    ]),
+    (-120, &[
+        "3e48439a18b2975ae751000000",
+        "-85d1f5e7f78e4c13d500000",
+        "59351ebb80810cb6000",
+        "-3232548baed00",
+    ]),
+    (-123, &[
+        "81125e179c0000000",
+        "4cf96e1680000",
+    ]),
+    (-127, &[
+        "f089e32ee01c7cea988f2142ef6fd17f",
+        "-c63c3c3d2e1b2cd55e31fcdac1438",
+        "47384efeb4c2a3b26d31a3530c",
+        "-1a8db7ac9196c1cdf",
+        "8706edaa44a5e",
+    ]),
+    (-131, &[
+        "7203bae00000000000000000000",
+        "-c30438bc0000000000000000",
+        "58ada4f2b9a00000000000",
+        "-246274244140000000",
+        "eaca7aa638000",
+    ]),
+    (-132, &[
+        "14e8e1f24d24341f4af1000000",
+        "b1aa252f675c128d84d00000",
+        "-44ddc1b913db3d86a000",
+        "-10d426fa2ceb00",
+    ]),
+    (-136, &[
+        "1e94955104c911fb2851000000",
+        "-5ed8d2ac915e94950500000",
+        "9bd87919231d86536000",
+        "-1cf58b9d501d00",
+    ]),
+    (-139, &[
+        "e4638b5000000000000",
+        "-2e01a4806c0000000",
+        "2b48852dc10000",
+    ]),
+    (-148, &[
+        "-6888495b0d9e678de7000",
+        "-8ce6ba1f308980",
+    ]),
+    (-152, &[
+        "56c3b4cbaac024664010962a59000000000",
+        "-40e936ea55250a1490347f42b80000000",
+        "217ff6256265ff9d4be6a353f000000",
+        "56843a2606fc6b0c228f700000",
+        "e14fcc23e9318f7f87000",
+        "-eb5aa0bbd09380",
+    ]),
+    (-155, &[
+        "1d86179d2dc0000000000000000",
+        "41e76249bb10000000000000",
+        "-96b26750c46c0000000",
+        "1584713f05b0000",
+    ]),
+    (-163, &["3a4b862c4b40000"]),
+    (-168, &[
+        "5fa67163006a1522ae9d7c51000000",
+        "-d0cdf57ce461b66a7d08c500000",
+        "1165b1eb5540a8194f36000",
+        "-6b582380213dd00",
+    ]),
+    (-179, &[
+        "342f6d22ed5a00000000000000000000",
+        "-48224c75d7bf89000000000000000",
+        "21bb85c7d6e9fac00000000000",
+        "-1d1ed0d19a9f000000000",
+        "18e9cfef3ef90000",
+    ]),
+    (-184, &[
+        "1610f8284c37eb47eba38751000000",
+        "1e887f45ea67d51370ae2b00000",
+        "12a25b551f5df3180dcb6000",
+        "-2ca123bb0578ed00",
+    ]),
+    (-187, &[
+        "-d079a7fd1000000000",
+        "3f14475b65248000",
+    ]),
+    (-195, &[
+        "-b830fcd60f9d000000000000000",
+        "1528a46fd3a45400000000000",
+        "14f7e3ad05bbdd00000000",
+        "9c9a487a35df0000",
+    ]),
+    (-203, &[
+        "6257655f9bbe40000000000000000",
+        "c5b722361bcf440000000000000",
+        "-44b336e7bd257140000000",
+        "17dac308d07c40000",
+    ]),
+    (-211, &[
+        "11290284dff8000000000000",
+        "3abd5f8f2b18c0000000",
+        "3922dfea0337c0000",
+    ]),
+    (-212, &[
+        "-2e26b89d7d252e6ed829a192e1160ccf7000000000",
+        "6fe420971026883392d219f08fceeeb80000000",
+        "-7e5ea4c390ab1fcff144eecb9c65a1000000",
+        "-1fa8d75e033b23ac9c35957e2700000",
+        "-7f05d5c9b80fc5010b50c9000",
+        "-3fa73430222919c80",
+    ]),
+    (-219, &[
+        "155fb9006602228000000000000000",
+        "-c9b1368a9ff02ca00000000000",
+        "2af6b60e1e44486c0000000",
+        "86a00f20f9b848000",
+    ]),
+    (-227, &[
+        "ef1e2515d12ca00000000000000000000",
+        "-1969629df4691da7000000000000000",
+        "382ad6296b94955bc000000000000",
+        "-84781d9278c8d6680000000",
+        "1385276f9b5cb00000",
+    ]),
+    (-228, &[
+        "160b5352b68761bb5db5f694f7af1000000",
+        "2c41c614bcc9fd2df73859f2f4d00000",
+        "-64c9a04e2865e62bbe2906a000",
+        "-15a9a2f29eba75eb00",
+    ]),
+    (-232, &[
+        "bbb30561bf86c1a06767409000",
+        "-20c85064aaf7b80e80",
+    ]),
+    (-235, &[
+        "9e1cba19fc02540000000",
+        "2c9fe1ad9bbdc30000",
+    ]),
+    (-244, &[
+        "-1b820dcf67709593e6fe42e3f854f7000000000",
+        "-1184c82c015bb31d3956fc80d2b9480000000",
+        "-5bf60d0519f68102997e22e05da1000000",
+        "-206521dd4ceaa5bd15fdd50d2700000",
+        "-4957e48a42a187c770efe0c9000",
+        "-6f415344ac90ec1c80",
+    ]),
+    (-247, &[
+        "-109cca1347184b3f2da9354798125542a8526163bbc6fe0f",
+        "42e093bb2edcec71d72706aa5c8f64794251c254066d5",
+        "1b18a44210507711282e29d56c095d1b00b3610ef",
+        "583be3ea523422efde7f274798150533f9d0",
+        "2df0eb3007495075296797f",
+        "964aed36dd8b9190ba",
+    ]),
+    (-259, &[
+        "d8299acdc1667000000000000000",
+        "4555dff9459882400000000000",
+        "-1308f2746e1ea9800000000",
+        "1eba1a7445b529b0000",
+    ]),
+    (-267, &[
+        "6b524505ba50df0d000000000",
+        "42b05c5abcb90b98000",
+    ]),
+    (-280, &[
+        "1460c0b1eefe78c5f4954627134551000000",
+        "-2150ee032f63caff6a913c2d737500000",
+        "363defe70eab1e0bf41306a3b6000",
+        "-e5449af994f80628d00",
+    ]),
+    (-283, &[
+        "28aaae27368b8600000000000",
+        "4b23f4e14346cac0000000",
+        "12f9d6ee5f2f577c8000",
+    ]),
+    (-291, &[
+        "4002f4c9d1470bb6cc0000000000000000",
+        "36f6c68691b998b988200000000000",
+        "882558960b2a300adec0000000",
+        "27d7ef08e7bdfbb98000",
+    ]),
+    (-292, &[
+        "-45d7b6c440d9b469779a74bcaa50f000000",
+        "223f21c3bd556d19bcf7d86d54d00000",
+        "-120b73c61dbac771edc057cc06a000",
+        "-2baee11096ee3b0feb00",
+    ]),
+    (-307, &[
+        "717090b6397708000000000000",
+        "-106d1800f24af9d800000000",
+        "aa780c7fb136223f8000",
+    ]),
+    (-312, &[
+        "129954c0168478688fb2551012b2054cf51000000",
+        "-6d4c881c0476b60c9cc93ef26c33d75500000",
+        "10fc7fa5c2631c34f78fdd7ca0b6000",
+        "-10a6600aa555aef5e6d00",
+    ]),
+    (-323, &[
+        "-578333dbfa233b8bad3d78000000000000000",
+        "d8e4619eec5622927b92d7a00000000000",
+        "-1865d5e606a2655b4ec340000000",
+        "2be9072f9d5d04e138000",
+    ]),
+    (-328, &[
+        "3fd2909d8fabd8bf5c63fa737d4ae51000000",
+        "a10c91773eaaeacb62b56d7d38bdb00000",
+        "8bc25e7ba1d5d819359911a99036000",
+        "-43dcb0055193483173d00",
+    ]),
+    (-331, &[
+        "ad1b3de53a4147e5c200000000000",
+        "4a76e3fa256094052c0000000",
+        "57fa48851695de1498000",
+    ]),
+    (-339, &[
+        "11ed3b924ee2c2ee3d7000000000000000000000000",
+        "13fa51d3615fa630466700000000000000000000",
+        "1807fa9c4edb73e27f7909000000000000000",
+        "-18d2b0962fb78c1a865af800000000000",
+        "99d1d7de0f8c1061c13d80000000",
+        "aece01b459ba0e48e0000",
+    ]),
+    (-340, &[
+        "1ee115cf787df40c678c00bf0b26a71000000",
+        "43cd9976d7a4a1b04d35e510b0cc06500000",
+        "-2909b85798d22bf97febe19c6b82a000",
+        "-be5c550ccf43377c7d300",
+    ]),
+    (-347, &[
+        "2063c61098d10c59b9c600000000000000000000",
+        "-37256a08f2581fc3d65185000000000000000",
+        "1a3fc3b2f40b6ed33d75981c400000000000",
+        "-17c65840a49fb4603817900000000",
+        "158897253ad88de0830000",
+    ]),
+    (-355, &[
+        "7e0166a5350190440000000000000000",
+        "-49ff84b50385adfdc000000000000",
+        "5631783073d0ba844fc0000000",
+        "2a1d0983f61dce12460000",
+    ]),
+    (-372, &[
+        "6ea257ac19369f8b2e54689d4cdf6803de9671000000",
+        "1337fc1edc5fb86586085a503b530c2ea02500000",
+        "-8ba08cb98aa892cd8d05e33170c62a000",
+        "-aae609d25d411b42b01300",
+    ]),
+    (-379, &[
+        "b9e5445040f5d43bd18000000000000",
+        "-5fe66b7d592d4c18e2540000000",
+        "12d6bba704e88e480440000",
+    ]),
+    (-388, &[
+        "-c47a701e7eb0f03846333028efad02d10f000000",
+        "9564bbde3000875315a893eaf8db858d00000",
+        "-3c5e33f523554c38c471a1e58d7826a000",
+        "-26c6fe41720224b8477ab00",
+    ]),
+    (-403, &[
+        "-15fb1d099925dffd2c0000000",
+        "7eceb03d850b48f4e9d0000",
+    ]),
+    (-408, &[
+        "8ba6d54ba1cef9bae8ce4bec2c1fbc466d117951000000",
+        "-e529199ce509046aa072f5a123663a3ad113500000",
+        "280d6bc6f04fedccb07c802ee9b54db6000",
+        "-bb4c242b3dd872942f04d00",
+    ]),
+    (-411, &[
+        "2fa7708777c7026a2f5c039000000000000000000000000",
+        "-c81f670546e6407338e747c00000000000000000000",
+        "9877801c236b2543953db18cf000000000000000",
+        "11bb44facb8c3513a25f67bfc00000000000",
+        "5b5fadccc96ed0b01f38b8a00000000",
+        "ec6905843fdce9d95c50000",
+    ]),
+    (-424, &[
+        "28cd923738f031994bf80c7a60c0f3a9fe21c1ad9000000000",
+        "-134b33656a0ce20ef086c38acd44c0a4b615f30380000000",
+        "278f27e551c19b9deb1e28972e835384a3d17bf000000",
+        "a25197cccaa01bcd5d7990387d39bee72e700000",
+        "fe525e54ebd0dc9f0ab25a2707584387000",
+        "-28241d88d41cc080ab896b80",
+    ]),
+    (-427, &[
+        "7a4e6eee22e91fbbe33c0000000",
+        "32717e4b1df0bb6980de0000",
+    ]),
+    (-435, &[
+        "-2310ef1b3a8e126ed0497c69000000000000000",
+        "1ec1481ada47826638ae5c4ee400000000000",
+        "41cd3826c39f4f0a66cb4c0c00000000",
+        "5c67218e69afe39e568b0000",
+    ]),
+    (-436, &[
+        "-20f3e7b9cfcb94402af46c9acd0195bc96d42a38f7000000000",
+        "aae5b95094dba3c0298f317ecc8fb7d2d82c5d2b80000000",
+        "-e1f8170ee87a66b7312cbba6552bf39d11ab9a1000000",
+        "1289f910e33a0e3eac340bdfd5eb981eaf5900000",
+        "-3e2673e4d846d1abb5c84b1b8fb80e8c9000",
+        "-63a064c322539933d9835c80",
+    ]),
+    (-443, &[
+        "107ffe8b7326341f8a26e125c7d8000000000000000000",
+        "-1b9ca61d0f241b553c7dfb1046cf000000000000000",
+        "7f48c2e09a213d07f129795c09a5400000000000",
+        "-9260134124e4d649fe4abd8080000000",
+        "a85463e7672245cf64758000",
+    ]),
+    (-451, &[
+        "aa31901912710a68d8000000000000000000000000",
+        "47945f636acc68918d7c440000000000000000000",
+        "1a35650b132dd7d1bd36233000000000000000",
+        "6d8f363f37f6ae455221a6c00000000000",
+        "744597199ab6e19240ca100000000",
+        "130ff0369c8d64a8d9b9d0000",
+    ]),
+    (-472, &[
+        "18d77d7875fa6eff36b7e0848ebf1d452f64e59000000000",
+        "51417e6f4f7e56e20a0616aa4b7f38864d871480000000",
+        "efa61925f6d645db25ec5bd33ff6b5c966093f000000",
+        "-487ebce1b1e0dc9c33a44ab1c057d2e96b8900000",
+        "d03d5041aa7ab084f74ade5aae18ed3f87000",
+        "-58873e0952ef2f8cc33e55380",
+    ]),
+    (-483, &[
+        "-33e3edbba59382c0833552873000000000000000",
+        "73375846fa62e903716ce84e4800000000000",
+        "1c163565bd9b09c6ece5a675f480000000",
+        "c335041ef2c487e0ac0360000",
+    ]),
+    (-499, &[
+        "359f3a07053ef194e2939349000000000000",
+        "-48fd45eecd90f6c7a44660d40000000",
+        "25ee01601775cd35648e800000",
+    ]),
+    (-515, &[
+        "6b6ed08160283ea0cd7823f05dc940000000000000000000000000",
+        "-1eefdf48026fd3a87e0192fbf01770000000000000000000000",
+        "ff4abd577ca8b98e01397d52a1852cec7000000000000000",
+        "abf5a9aef85d0ac61b0506024d85a5ac00000000000",
+        "-23474f276bcf296c57069d9a82400000000",
+        "73cf70cc5fe32dfd3bad190000",
+    ]),
+    (-520, &[
+        "6feb9db525eabead790b21c1eeb212695b542b651000000",
+        "-d07e3ca15d0db8313683685e5db779ed094dda500000",
+        "82bdbc5e3c80aecfb3b629dba43bea667436000",
+        "-a3906e1b62ce4794f2ab60bd00",
+    ]),
+    (-523, &[
+        "985abefcef12880e8c68000000000000000000",
+        "-5fb0b803261d068163213000000000000000",
+        "275d52f5369f7f6aa56492400000000000",
+        "-2da2e999866bc2497eb03b80000000",
+        "c90d23109c125e46fc65b08000",
+    ]),
+    (-532, &[
+        "-32fd627663bf405047f90c6eb846e951b0858f000000",
+        "db723fe93cc9ded2ca8be21f907c0a48c9ad6500000",
+        "-1c091441d601b1265e05b41db8408118b602a000",
+        "-174137e05126fc7fab6f93ad300",
+    ]),
+    (-547, &[
+        "3eabc1399b90f037db4aa00000000000",
+        "-1ae85980856a819788c27040000000",
+        "4021e0d710219937eb86a058000",
+    ]),
+    (-555, &[
+        "-16c86b9374828cabd51364fbf040000000000000000",
+        "d318632eb2369f83ad8943649b9f2000000000000",
+        "528c7d34fb2b219381a19e17d5d8c0000000",
+        "6d8a75c9190c9dae3b12d840000",
+    ]),
+    (-568, &[
+        "baec812f1ac9609d29f7d0e137768a629479f751000000",
+        "-361c189cda8b6b5bcb067deb1c8e4fef1abf6d500000",
+        "41401542249a89fc4340643818c346bdfbd4b6000",
+        "-10352cca67633d03cc14d75ded00",
+    ]),
+    (-571, &[
+        "28d917178e5474bc083516ce00000000000000000000",
+        "-b2a9a0be8a5d7ac2a03f4fa6d0000000000000000",
+        "c53971484d571fe863dbc5d469600000000000",
+        "267c95fa73b28b2c2f8277a13c0000000",
+        "13befe54e19f93ff30ec2eea8000",
+    ]),
+    (-595, &[
+        "-10028196bcd72acadfdbac5c0000000000000000",
+        "15a931fdb2b20703acbc57e736000000000000",
+        "19b85b5a51a546509fb0c892b340000000",
+        "5e1a00cd92c91420ef7977390000",
+    ]),
+    (-619, &[
+        "4321b0e95c50b976788f1c09a8de00000000000000000000",
+        "4c284516973198aaaba1f44de81e0000000000000000",
+        "39250eb817d669cdfa81a46fa45d200000000000",
+        "-ffb81a138d4ecf5048caca376840000000",
+        "1b2adfcc042613688b5a6b97b8000",
+    ]),
+    (-627, &[
+        "-35f480a43240f153d15e770e7000000000000000000",
+        "5c314d557f416cd792cb6eb81d28000000000000",
+        "9fcf15dcd941e134567e97ecac9d6000000000",
+        "2cf26f0e06e680b83952bf1258000",
+    ]),
+    (-628, &[
+        "-1d65350c2e7fbd931be042574afecab8b02acf1f751cf7000000000",
+        "5750cec8d67052f2c101e0a3302f7a8bb1eebf73515eb80000000",
+        "-709339f1944ccf63568cdddd548d2e2322857906d15a1000000",
+        "-28f2187a9d1505aa26d18d6c518560ffaef920f42700000",
+        "-52c38dc27faca9b88f0c15b549a39875808cf0c9000",
+        "-2fdb10c2ce84d759f26d698ea9c80",
+    ]),
+    (-643, &[
+        "dd0451828fc4d5f5f6b92b93ba00000000000",
+        "-1283e0c32ba00f434a7325d9aa40000000",
+        "79dbf54dedd980184fe7354258000",
+    ]),
+    (-667, &[
+        "-c7f56d842bb2d2aa182071000000000000000",
+        "-1b04053392c50b2195edde9400000000000",
+        "-2cfe21a41c2f5f1d020976200000000",
+        "213a22e4f57cbcd8624f9b83010000",
+    ]),
+    (-683, &[
+        "f8764dc4ebf17daed31c99f06f76da5d95f000000000000000000000",
+        "-c372a8abf9bb99767528001a658dcf7ea7c6cf000000000000000",
+        "2ab5f82903f27b460fa6790fe6d6a79e2033ec000000000000",
+        "-3d1aefa4544df654df916c722a18684c80000000",
+        "576bed783f9ed7876a989fc1d00000",
+    ]),
+    (-691, &[
+        "1caf8c28e6347fad32473a86e90d9f000000000000000000000",
+        "-65bccc303c4e6a77148d1103e7d19ad000000000000000",
+        "6f053081c85a5594e0ec9bddb75f89c00000000000",
+        "4cf4070bf7563e7a39743cc1b36d00000000",
+        "8d3357c6cab4254b6896e0aebd0000",
+    ]),
+    (-707, &[
+        "19d660be2de29ed2f068e24cc222a8eccf28c9000000000000000000000000",
+        "1000f737a90860c0d86768320273035f5e442f500000000000000000000",
+        "49884c89b093b7913ac25144126a3f79b2018227000000000000000",
+        "1cf133073cc98206b24e22cd70ff9fa9a918bc1400000000000",
+        "-19b4f4ca65222a0627fd79bcd1099cd4400000000",
+        "16d54cb77b3a63661645d1016f70000",
+    ]),
+    (-708, &[
+        "24a53f1143b7a238fb93d69444c228a7a04a68df7400dd4a9717af1000000",
+        "22389064ab88d75bd7b1045a47b10e64fd66dbc03b6cea9cf4d00000",
+        "-1dcd94cdbddbb95b3ca1a9e9a3c3a807d25ffa8906a000",
+        "-1838e3e534454b4970ffa50e1d5eb00",
+    ]),
+    (-715, &[
+        "9065ea39f57e39488da3200cbf000000000000000",
+        "1094975a286690b57d54cbebad24c00000000000",
+        "2b28fb50ff7e9a8e97bc397343b5a00000000",
+        "249466d55c551a6f7fb23d2d5790000",
+    ]),
+    (-723, &[
+        "1c9486e455ece7c65dc4e57bebaa7e00000000000000000",
+        "-2e9b704d522da70426e0b5643cc75a04600000000000",
+        "5a04371c5cb3955d03b32f70f2f63e4bcc0000000",
+        "3a72c017e695283e5c8844f4a778000",
+    ]),
+    (-739, &[
+        "b4aadf86fda5e5c4c8c6237d65a7000000000000000000000",
+        "-19c08ec08014cd796c56d1a821ecfee3000000000000000",
+        "168b94ce393192221079a8eb78af8c64800000000000",
+        "-175f1d83fe0bae68a2c877d961373780000000",
+        "9413607d011db4fb1311090f2a60000",
+    ]),
+    (-760, &[
+        "220f5c5d25d40d0c077b413378b3d956e929ff601fb5d800f51000000",
+        "-154d09d198ad389e4d107bb18b9ee1c9f2f7706d7e98a335500000",
+        "ab96f91f4bc35e79974c0f131a5b676e336b3be640b6000",
+        "-1ee0fb2f21146b5a1e05305c12ba6d00",
+    ]),
+    (-763, &[
+        "d4550bcc9cd18f3974e661dc0000000000000000",
+        "a743f115a88b9c2a9806a3eb12000000000000",
+        "870cfdef4fed04b580ff168aed8ac0000000",
+        "24a102cd9c9ce7dbf1a5bda7b3440000",
+    ]),
+    (-771, &[
+        "a7e90ba1def75573c4b5074d144733f5a3a0240000000000000000000000",
+        "-35066e09e2b3e564614a1446df0488bb0f0483b0000000000000000000",
+        "467a4f05b2a63f9dae919da454d3afc1db0ad537000000000000000",
+        "135b5e73f97014957ee83a136500b484015ec800000000000",
+        "dec3facb54d7e74b23cbec2cd7f904bc6fc0000000",
+        "39a94931ec7baa200a77c93711090000",
+    ]),
+    (-772, &[
+        "-9d215e50a91d4e7a8b0eac3684ba5c89260b2ab6014d10f000000",
+        "-1078848b1b73d27207fe5746e71ed43d430c5c0d911a7300000",
+        "-1dc9d3e829b248a9c3a08ac1b9ab197c21c3cfe06826a000",
+        "-3d042139f3e077649f376291ad17ab00",
+    ]),
+    (-787, &[
+        "4f7a53f6b0262b5ecd45ef5ac9a00000000000000000000",
+        "30074133ba6e0353cea896e365b27000000000000000",
+        "b266c349214ee3d91fd3b2d16e2c0400000000000",
+        "3181dc2df759accf7648224c3822c00000000",
+        "8de48bc8007d29caa7fa7b1cceeb0000",
+    ]),
+    (-795, &[
+        "fbd8b747bcf3bc8b79ff7a426dcae8188bb000000000000000",
+        "f7388523999581d40d63103187de4741640bdc00000000000",
+        "53ece3231666b91c4cd43578570ee36b67300000000",
+        "ddd2cbe266d65d1686e5ed6a12cd0000",
+    ]),
+    (-808, &[
+        "1b216bbcb58c8a870d41a158935d55ffd4ca7e1bdb0908f7822d9000000000",
+        "1a3e6bf0078c3e593adad091cec4fdfa13c560dee9a86c49af7c80000000",
+        "42e54486320e132bf0b876b09fab5382febbe4ffd6a8f95affbf000000",
+        "-fc4077e5e92574312cd381f6adeb9c70a57ffe003cfbee1900000",
+        "26138a626062c270ff4ebd08ef03c0888107f5c8814387000",
+        "-1c85044f8d1622d3f598233c01106eb80",
+    ]),
+    (-835, &[
+        "30b5321faecfbb020c6792a9997000000000000000000000000",
+        "-11f3a8218aca9e0c14bbcaaeb41de80000000000000000000",
+        "4595f8197ae967015aab50c34518443000000000000000",
+        "-5856221f9c4dd4bf2a47f5e135dec76c00000000000",
+        "23cf5195c87606d0f4b349e040da8eb100000000",
+        "7d41a097c5c23e344f8ed9f4d8d3f0000",
+    ]),
+    (-843, &[
+        "16fe3d3ea904f0d3752e8c2bc0913f0ef1ef200000000000000000000000",
+        "2b5e43d8928b6ae173e694eb4ed716f364b04f8000000000000000000",
+        "21baa0426ca2edf34b5948d22798b10e71f7575000000000000000",
+        "60204ecb243ce2eed15154615ba83106069c7c00000000000",
+        "b002bea0ae8e479bd81475f9b2827818d87080000000",
+        "c14acd669202350d7bc1340f8a4d18000",
+    ]),
+    (-856, &[
+        "4e7324aad14421d9a8e3edee44ea72375b2522c0652d3af31b75f5e86c259000000000",
+        "366e05e7f1e2b14846bb44b20705a9923610cad846857344cb1961b5b5480000000",
+        "f74f9879a2da6096fc16721f688ad7115cd95e16884130de8976f6d3f000000",
+        "-1a9fcddc7b382d88491e4bb6609eaaafd66638a53f72f7940a0900000",
+        "781a957a4b9530e7e72c8bd2ab1ddd8d44498bf3d637f87000",
+        "-1857c474bb59cdd75bda1ee3c133e11380",
+    ]),
+    (-883, &[
+        "1d6cf1e6a5d7568dcabf33fc4ecae00000000000",
+        "-6d06aa164da28a403b06a5301325940000000",
+        "6692cd3b8067ced31735672aed03428000",
+    ]),
+    (-907, &[
+        "1a209f7590a2612908db7d8a1600000000000000",
+        "1c1c85b4b678ec79af0f1683f94b000000000",
+        "169ad248ed2873282486100923f5f000000",
+    ]),
+    (-947, &[
+        "ae72dc3c404cb93ed2cea720a65954c31e169e8be2e946f8000000000000000000",
+        "4e53bd6a2b32f43a7b4c9dd0d1fb22b7128672290e782b9000000000000000",
+        "16020be1c789005a69047e91299b8c62c40072cf93a450d400000000000",
+        "-3e9832d7c45a55924a63677fe015e3a7e33b94a80000000",
+        "b20794d6c75649a425812bdc1c591338000",
+    ]),
+    (-955, &[
+        "1112941b349e201c94ef6be16dce1469a7d515000000000000000",
+        "1537394034319a0de1298fae83a361ebc8a6000000000000",
+        "10f468c8fb20f4eeccfe323c6465e91e17880000000",
+        "10b962c401b51bf544e97070f805c5ec0000",
+    ]),
+    (-1003, &[
+        "2832df2109693a1937c806a798200000000000000000",
+        "37d0e5012cf801a9a5d14c9eb124e9e200000000000",
+        "-23d1d3eeaa4c84246485b49e5bd096b340000000",
+        "ba30e90ad19d086718dc71c30cee97f58000",
+    ]),
+    (-1012, &[
+        "-1d9b8d0dbf0cb4a6e22825bf5631c8ea2cc2aa495307991aaefd8f000000",
+        "2e1fd08c54038b317c03f093780e553ffb245fa1e0651db5953e500000",
+        "-135b76560dba2ac219f4553a707d2bef09049c17f64a139ef42a000",
+        "-122a9ad0d3cb25192049f10fef19975445300",
+    ]),
+    (-1027, &[
+        "2b2e1d41225773f04086b6172057ac99879000000000000000",
+        "6b66d15d2b698de1d91d4f1d47c9c819653000000000000",
+        "-1c6d8a0994b47754f6ed65935b48d2643a80000000",
+        "25ff4c87e32e742f8ddcada5d117971e40000",
+    ]),
+    (-1048, &[
+        "f296fc83ba590d0c6d8d66d9d696c70b7ce74601b54a1b357e457c4cf07a59000000000",
+        "-36053b4a36e5077fc2a8ccd5de760abad571115e339ca81e1b41016ab7b2b80000000",
+        "405d294baccec7e6b63ddaceecc566508bcf5d8cdd775b7ee4652a74c53f000000",
+        "ea69832c1ce59abd6a55e8cd25756a373dd95fb8ccf9e097d0e5ef700000",
+        "11018a984a3313e031577319789b7ab0ae01624c670cc383aff87000",
+        "-69cf0d8b43af59012de84190aeeb00d979380",
+    ]),
+    (-1051, &[
+        "1444c2998412c8b408ca848cf8bdb560932f4a1c0000000000000000000",
+        "5c98fac580b6e784a08e6208a6f45b25d439940f000000000000000",
+        "34518d31e1fde8f2431e70e1a78eb7861fa03b00400000000000",
+        "c5cfd7551b27481e212e27fa0662065eec9600000000",
+        "7a602e1fc74699c162ceae1800273f3f70000",
+    ]),
+    (-1059, &[
+        "736f8e461069817f4838b7fa2476abf094d18725ee973000000000000000000000000",
+        "-24f4ca29f9e4b4c09772f495d7221d0c971076896f6867000000000000000000000",
+        "c9912c9f8831c2394529f7cfab360230a09ad1082f7dc838d000000000000000",
+        "-736a454286adccb94b344738d25c5649f3cd9802f3f37000000000000",
+        "193e0be46bd3a08880dede670e190bd300a2d07e1a80000000",
+        "b42f50552fde752c4788cb7f4663ce5b80000",
+    ]),
+    (-1099, &[
+        "9d155762662b59ba93ac871947f770bc45afbcf79000000000000000000000000",
+        "-5a886cd6c970841a98b73deef96211a3751a1057af00000000000000000000",
+        "dfb4c0e26774543a6833d01fc392b953a58e4eaa3ef000000000000000",
+        "5178ae28fd8a53496e174fc32a30ca7e82ffd1a8c400000000000",
+        "-1fe6fa1fdc689c407fc40293d9f4c6c3f429ea00000000",
+        "4c4505d91fecaa76c762ace602283bac830000",
+    ]),
+    (-1108, &[
+        "-209141549318d5c70e4fbeb9441551a43f991cf120f9f682a22a4aa3554f7000000000",
+        "1afc8d7400b97d182bdf1b70a9f1a423a9ed2056fd8b1c07acf7231c46b80000000",
+        "-35d762f305b684fb6b74267405d09d0fe7033caa8b72a9865a7d055da1000000",
+        "-1d0eab732fde60509dc21cc6d40fd647431055f4fba15ca71a6d2700000",
+        "-4ed255a5811e2bdb78a92b0bc73f6a4307bff093b7e9937e3be0c9000",
+        "-74ba7eb989d27b6a9da047b7786a1695fc1c80",
+    ]),
+    (-1123, &[
+        "4664ecd62220a17623ca79a77f9d03a9f40000000000000000000",
+        "-7650b2c12f0f43bd70e4ee4430be391034db000000000000000",
+        "9b695b1dbe112ac84de7265077310d967e0aa800000000000",
+        "3bbbf251f80c28d6420e30ab5952ab56148e80000000",
+        "ec5a0b61c56bc30ba636fff7f3739471fa0000",
+    ]),
+    (-1147, &[
+        "-b621846c67147f6d135fd558a82319e8d74c0000000000000000000000",
+        "208712138541872c83aaec7c47951c8bf9fcf0000000000000000000",
+        "-1591f8c94941eabd34aabe4de22d481082de09000000000000000",
+        "290345972cca5bb56779580147f1fc1fdb5c88800000000000",
+        "-13663ce692b86052516e511673a840879628140000000",
+        "2d3ad3b7dfe6515024b713957e773eda89f0000",
+    ]),
+    (-1192, &[
+        "27a1593ea721f5cde4c52ecb9fbc6613be964371f559be15b68b6a7c00ed9000000000",
+        "-2471e11b518c7329910a771f093940fee755130c0e0a2d055111112246c380000000",
+        "cb22071a00619ca39eb59f7416469c7031ad455a08b7342ec02bc317bbf000000",
+        "-ff1c42f82b7fa1b62d8d8644684100d1b96fed310c8a8ab0efc91db9900000",
+        "6990e20c7c8abee9833edbc6a1540e0eb4d53922f843a6cf9e200387000",
+        "-1656278008303aed241c7e48dcc530b1e2ad2b80",
+    ]),
+    (-1203, &[
+        "24807787bf1436a69900847d340add89531e413ee200000000000000000000000000",
+        "3bb745bc5a64dcd2e36721dc01909d345f8c024b947fc000000000000000000000",
+        "5b99d96ce3ee5449935a95a1a78acf73689d8c638aeb86378000000000000000",
+        "-1e0d411e9c144809f009c501e7e5d7c8eec986b7651b5df600000000000",
+        "30943852ced669c316d75741dee3e6ae87b694420b5cdc0000000",
+        "24cd338ae1ef15963bfcbb19ed91877de8f58000",
+    ]),
+    (-1219, &[
+        "-ceacfcdf539f5f996eff69534ec9837bb4d2e77000000000000000000000000",
+        "30b03d9790b2e9de4f70ef7210e2c1d054079cc0aa00000000000000000000",
+        "2a8185a4ffe0d0918e4570d14a5737dca92ab33203a3000000000000000",
+        "2e8ffa8dbb32d563b31fada3399b5008c9000401ecb4400000000000",
+        "-601c12265cf183d7463e70079dd0ac565741d8a700000000",
+        "4bc6441f455ce4567d6952b5735395c60e4b0000",
+    ]),
+    (-1227, &[
+        "16c30c656f3c075f42c17a0de916abda7a40616ce05f40000000000000000",
+        "34f81e6c41ac1caa7b527a3e7f53884bcef7cf851df00000000000000",
+        "cd76627adab025c5a6f491deae35e4e62fe5024409177c0000000",
+        "6c89919f5a1a88463ba748131161128c8ea80000",
+    ]),
+    (-1243, &[
+        "4dd8789c920a600aaa817c2704ea97d000000000000000000",
+        "-23abcfafead2b11682b04ce9ad09fcef41b000000000000",
+        "46e2d068b726f4c03795a125792bac88d0b840000000",
+        "dde87cce1e728059a9384b81ad8de50953930000",
+    ]),
+    (-1267, &[
+        "6088e80612ce530268603130608e122d201000000000000000000000000",
+        "-247920af97d93063ca7b4005b56c64d49916600000000000000000000",
+        "3dba21f1971a38acb21373a2764fb80d33a65db000000000000000",
+        "-c13a2db619ce6361eccd4490ad5a6b5bb30eb0c00000000000",
+        "14ac13ee1500b844eb3bfa559ef3ff2e56e1f3300000000",
+        "28330d93cec976fcb0ec93823247cfbe2814f0000",
+    ]),
+    (-1315, &[
+        "41e45d70a2f9e0e49ccea40b926a5c369dc4e690de640000000000000000000000000",
+        "6d02ac10b80eb78d859ef2492de2bcf06cb9acaa20f4800000000000000000000",
+        "1225d06470c3f2c2989a9a31526abfe09ac2adf60deecf7000000000000000",
+        "-2efd681b3186be31ff8a5b873a6f814e9635d80e3ae6c00000000000",
+        "2560bbff5fb1a6a4c95f9124931c30f38ccdc1f26400000000",
+        "147cbae32832885eaf2b6c4527242b1dde1adf0000",
+    ]),
+    (-1347, &[
+        "1f20b09350ea968ce60fa1a6367860ced5920617001a4fb81e00000000000000000000000",
+        "-bfead9e798e5591b5798002b5c5994ebc0f8cda807e014f7b9f8000000000000000000",
+        "27ed27a683a0eb7add4cb070efc035932cb4daf1e6cbf4aa415e1000000000000000",
+        "3aa862a9c3014b916d24e94d8548438df88d977472375f213fc00000000000",
+        "376d989d982a38c3292150d047b0f964ef03ba88dcbc793580000000",
+        "5141a304a16425ac2812d61d82a2b5e25982788000",
+    ]),
+    (-1363, &[
+        "-87eb6268d6c876a002d83dfaec75ee95f7a7f19d000000000000000000000000",
+        "32c253f1a268e4e7dee2767806bae68b73dbadffa300000000000000000000",
+        "-a155b489ce3c2cf0bf94e043d755fb7b217525d651b000000000000000",
+        "8d22f4ed84b0657700a2fadabc49ebbdfb3bf2397c800000000000",
+        "-43b693810cea067e297be61dcca886fee8c1b92980000000",
+        "a0d5a171aa7f52d0df1fdde34156b227cc8d060000",
+    ]),
+    (-1387, &[
+        "14704844080517e9caeadef51051d1c2f768000000000000000",
+        "5d830678a03dc591a4a15c7e279ec8a6784e4e00000000000",
+        "57ec6685bc6698ca2d4b4455462c57f452146fc0000000",
+        "1bc8bf083ad1f82e8f8c07c75e10dce9468eab08000",
+    ]),
+    (-1411, &[
+        "9318f1874bcb338fe8ac052aada540969319f1013866f40000000000000000",
+        "5198bdab0224e2b475748d95a2c7724a193cc978724928f4200000000000",
+        "c2d6994dda4ae273db515bb534df1acbdcdaf89788ac0000000",
+        "4c2031c0bfa7d0f9376d871fe69ba7105924e298000",
+    ]),
+    (-1432, &[
+        "1f36ef9746a34794a9b5cebdbdf053239ef14eb3b6d276978ffcfd58abfa325287659000000000",
+        "-8a13c58eb158e88b47dc73d87430a1f329115e76cc075784ef617f52e91a8e96c6b80000000",
+        "1487c506b219e30440cbe10ddf321081d75ac06749756ee9dbd053ea0b37cd2113f000000",
+        "2431ad95b24c72ae97bd0f67578966f09018fc2573ca3e6fc03392da310faf7700000",
+        "268e00e6e38194257390c80808b6aab856a49bba6428f413b8515c4566bf87000",
+        "-b69a0ab211ea43d00c4086d24afbfbd656a7d78d380",
+    ]),
+    (-1435, &[
+        "-244fc2cd91039b9809155168a473086c76213e13e3ca37000000000000000",
+        "5e3676f98059c27c84e69c9fa976e5c37a43ea3a7f1df0000000000000",
+        "4171319edcef23cb11940fe67dd1f825f4f6068e884d80000000",
+        "cecdcc9129638f6bb53432f7f0182a6c13e75600000",
+    ]),
+    (-1507, &[
+        "8fd6fafec689528cfeca51eb3bffee1911eeca5b000000000000000",
+        "-51f9cdaaa58bfb878e1406a9f7b99e67a54b6359c00000000000",
+        "96a2e57e41157293c8207d64c1ed902034036e8f00000000",
+        "f6b568b7e3a61e1b0fc752e9b519ebc26130a65b0000",
+    ]),
+    (-1555, &[
+        "19f9c48ff374592658410f82d72598ffb050ef84051fb000000000000000",
+        "-5b4705d2f31b56d9198135974ae42ec070ed53e20b5db400000000000",
+        "58c7143f520e69ab61b009a400e2f9e44ddabad1080d5300000000",
+        "69ea61b7cc70fa7b46a3bed5a2c0fee912b784d310000",
+    ]),
+    (-1563, &[
+        "1d500cef3b86a3814d09f4279d7c6858f7c4f19468400d000000000000000000000000000",
+        "-6407c249b033d6e0e9a414d0cfd8ee9975a688cf25f499980000000000000000000000",
+        "56c479fb4a2bf518300b26a42c6fbc3217beea3db8392dc3e6c0000000000000000",
+        "-51db0936f2ecf3efa687a1e439d6a4d071440b8af4b7f83cf4a000000000000",
+        "78a3f9f9dda316b39c867f5622a206577ec55b4e9d38abe8d897c0000000",
+        "919b3138dc03d92fc32a0f417f21b363e3f74894e0000",
+    ]),
+    (-1588, &[
+        "-14fd06847b28a94e1ebcc19d5467c7b3b6fc1f1575b8f9ba82c8cc64dedbc6e985139fd0edca8f7000000000",
+        "6e15861ec610bc9add2c6c69f92309d7987a22fffa10dc79a3e25340734909f94ecd828d1a2b80000000",
+        "-5bfe292b9f652c05c852606f70d8ef3713dc860343ebfcd053d1d11e1297fd13100ae0c49a1000000",
+        "-2f8be484cb81a1bd57269b06afad1083e20133e76377e184b62d14c0deaddde0dd82a700000",
+        "-791671b0e7354b6eb610c2c58e769d397d248b79fc6bb3aab318e372936bdc8c9000",
+        "-1879c0b350cf434989a6d757326dba11d29299f6465c80",
+    ]),
+];
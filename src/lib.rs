@@ -1,107 +1,1020 @@
-mod rng;
-mod algos;
-mod utils;
-mod bigint;
+pub mod rng;
+pub mod algos;
+pub mod utils;
+pub mod bigint;
+pub mod barrett;
+pub mod sieve;
+pub mod chains;
+pub mod certificate;
+pub mod sha256;
+pub mod hilbert;
+pub mod ecpp;
+pub mod special;
+pub mod primality;
+pub mod factor;
+
+use std::sync::{Arc, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use algos::PrimeResult;
 use bigint::BigInt;
+use barrett::BarrettCtx;
+use certificate::{Certificate, CertificateChain, PocklingtonStep};
+use primality::{Bpsw, PrimalityTest, PrimeInt};
+use sieve::{Sieve, sieving_primes};
+
+
+pub fn primes_16bit() -> u16 {
+    generate_prime(16)
+}
+
+
+
+pub fn primes_64bit() -> u64 {
+    generate_prime(64)
+}
+
+
+
+pub fn primes_128bit() -> u128 {
+    generate_prime(128)
+}
+
+
+pub fn primes_1024bit() -> BigInt {
+    generate_prime(1024)
+}
+
+
+const THREADS: usize = 16;
+
+
+// runs `search` on several threads and returns the first result found.
+// the other threads are expected to return None soon after `stop` is set
+pub fn parallel_search<T, F>(search: F) -> T
+where
+    T: Send + 'static,
+    F: Fn(&AtomicBool) -> Option<T> + Send + Sync + 'static,
+{
+    let stop = Arc::new(AtomicBool::new(false));
+    let search = Arc::new(search);
+    let (tx, rx) = mpsc::channel();
+
+    let mut handles = Vec::new();
+    for _ in 0..THREADS {
+        let thread_tx = tx.clone();
+        let thread_stop = stop.clone();
+        let thread_search = search.clone();
+        handles.push(std::thread::spawn(move || {
+            if let Some(result) = thread_search(&thread_stop) {
+                let _ = thread_tx.send(result);
+            }
+        }));
+    }
+    drop(tx);
+
+    let result = rx.recv().expect("All search threads stopped without a result");
+    stop.store(true, Ordering::Relaxed);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    result
+}
+
+
+fn search_prime<T: PrimeInt>(bits: usize, stop: &AtomicBool) -> Option<T> {
+    let primes = sieving_primes(bits);
+    let two = T::from_u64(2);
+    let last = T::ones(bits);
+
+    'restart: loop {
+        let mut candidate = T::random_odd(bits);
+        let mut sieve = Sieve::new(&primes, candidate, two);
+
+        loop {
+            if stop.load(Ordering::Relaxed) { return None; }
+
+            if !sieve.has_factor() && !candidate.is_perfect_power()
+                && !Bpsw.test(candidate).is_composite() {
+                return Some(candidate);
+            }
+
+            // odd candidates below 2^bits - 1 cannot overflow
+            if candidate >= last { continue 'restart; }
+            candidate = candidate + two;
+            sieve.advance();
+        }
+    }
+}
+
+
+// prime with exactly `bits` bits for any of the supported widths, the small
+// primes are sieved out, perfect powers skipped and the rest goes through
+// baillie-psw
+pub fn generate_prime<T: PrimeInt>(bits: usize) -> T {
+    if !(16..=T::BITS).contains(&bits) {
+        panic!("Prime size must be between 16 and {} bits", T::BITS);
+    }
+    parallel_search(move |stop| search_prime(bits, stop))
+}
+
+
+fn search_safe_prime(bits: usize, stop: &AtomicBool) -> Option<BigInt> {
+    let primes = sieving_primes(bits);
+    let two = BigInt::from(2);
+
+    'restart: loop {
+        let mut q = BigInt::random_bits(bits - 1);
+        let mut sieve = Sieve::new(&primes, q, two);
+
+        loop {
+            if stop.load(Ordering::Relaxed) { return None; }
+
+            // q and p = 2q + 1 are divisible by r when
+            // q = 0 or q = (r - 1) / 2 mod r
+            if !sieve.rejects(|r, q_mod_r| q_mod_r == 0 || q_mod_r == r / 2) {
+                let p = (q << 1).increase();
+                if algos::miller_rabin_test(q, 10) == PrimeResult::ProbablePrime
+                    && algos::miller_rabin_test(p, 10) == PrimeResult::ProbablePrime {
+                    return Some(p);
+                }
+            }
+
+            q = q.increase_by_2();
+            sieve.advance();
+            if q.bits() >= bits { continue 'restart; }
+        }
+    }
+}
+
+
+// safe prime p = 2q + 1 with q prime, p has exactly `bits` bits
+pub fn generate_safe_prime(bits: usize) -> BigInt {
+    if !(16..=1024).contains(&bits) {
+        panic!("Safe prime size must be between 16 and 1024 bits");
+    }
+    parallel_search(move |stop| search_safe_prime(bits, stop))
+}
+
+
+// first probable prime in start, start + step, start + 2 * step, ...
+// that has at most `bits` bits
+fn first_prime_in_progression(
+    start: BigInt, step: BigInt, bits: usize, stop: &AtomicBool
+) -> Option<BigInt> {
+
+    let primes = sieving_primes(start.bits());
+    let mut candidate = start;
+    let mut sieve = Sieve::new(&primes, start, step);
+
+    while candidate.bits() <= bits {
+        if stop.load(Ordering::Relaxed) { return None; }
+
+        if !sieve.has_factor()
+            && algos::miller_rabin_test(candidate, 10) == PrimeResult::ProbablePrime {
+            return Some(candidate);
+        }
+
+        candidate += step;
+        sieve.advance();
+    }
+    None
+}
+
+
+// random primes of an exact size, optionally restricted to
+// p = residue (mod modulus)
+#[derive(Clone, Copy)]
+pub struct PrimeGenerator {
+    bits: usize,
+    residue: BigInt,
+    modulus: BigInt,
+    top_two_bits: bool,
+}
+
+
+impl PrimeGenerator {
+
+    pub fn new(bits: usize) -> Self {
+        if !(16..=1024).contains(&bits) {
+            panic!("Prime size must be between 16 and 1024 bits");
+        }
+        Self { bits, residue: BigInt::from(1), modulus: BigInt::from(2), top_two_bits: false }
+    }
 
+    // forces the two most significant bits, so the product of two such
+    // primes has exactly twice as many bits (fips 186-5 b.3.1)
+    pub fn top_two_bits(mut self) -> Self {
+        self.top_two_bits = true;
+        self
+    }
+
+    pub fn congruence(mut self, residue: BigInt, modulus: BigInt) -> Self {
+        if modulus.is_zero() || modulus.bits() >= self.bits {
+            panic!("Congruence modulus must be non-zero and smaller than the prime");
+        }
+        let residue = residue % modulus;
+        if utils::gcd(residue, modulus) != BigInt::from(1) {
+            panic!("Congruence residue and modulus must be coprime");
+        }
+
+        // candidates also have to be odd, for an odd modulus m use the
+        // equivalent constraint modulo 2m
+        if modulus.is_even() {
+            self.residue = residue;
+            self.modulus = modulus;
+        } else {
+            self.residue = if residue.is_even() { residue + modulus } else { residue };
+            self.modulus = modulus << 1;
+        }
+        self
+    }
+
+    // single threaded search, returns None once `stop` is set
+    pub fn search(&self, stop: &AtomicBool) -> Option<BigInt> {
+        loop {
+            // smallest candidate >= a random `bits` bit number
+            let mut random = BigInt::random_bits(self.bits);
+            if self.top_two_bits {
+                random.set_bit(self.bits - 2);
+            }
+            let mut start = random - (random % self.modulus) + self.residue;
+            if start < random {
+                start += self.modulus;
+            }
+
+            match first_prime_in_progression(start, self.modulus, self.bits, stop) {
+                Some(prime) => return Some(prime),
+                None => if stop.load(Ordering::Relaxed) { return None; },
+            }
+        }
+    }
+
+    pub fn generate(&self) -> BigInt {
+        let generator = *self;
+        parallel_search(move |stop| generator.search(stop))
+    }
+
+}
+
+
+// p - 1 has the large prime factor r, p + 1 has the large prime factor s
+// and r - 1 has the large prime factor t
+#[derive(Clone, Copy)]
+pub struct StrongPrime {
+    pub p: BigInt,
+    pub r: BigInt,
+    pub s: BigInt,
+    pub t: BigInt,
+}
+
+
+fn search_strong_prime(bits: usize, stop: &AtomicBool) -> Option<StrongPrime> {
+    let one = BigInt::from(1);
+    let two = BigInt::from(2);
+    let half = bits / 2;
 
-fn primes_16bit() -> u16 {
     loop {
-        let num = rng::u16() | 0b1000000000000001;
-        if algos::trial_division_simple(num) == PrimeResult::Prime {
-            return num;
+        let s = PrimeGenerator::new(half - 16).search(stop)?;
+        let t = PrimeGenerator::new(half - 32).search(stop)?;
+
+        // r = 2it + 1
+        let i = BigInt::from(rng::u16() as u128 | 0x8000);
+        let r = match first_prime_in_progression(((i * t) << 1) + one, t << 1, half, stop) {
+            Some(r) => r,
+            None => if stop.load(Ordering::Relaxed) { return None } else { continue },
+        };
+
+        // p0 = 2 * (s^(r-2) mod r) * s - 1, so p0 = 1 mod r and p0 = -1 mod s
+        let s_inverse = utils::pow_mod(s, r - two, r);
+        let p0 = ((s_inverse * s) << 1) - one;
+
+        // p = p0 + 2jrs, starting from a random j that gives `bits` bits
+        let step = (r * s) << 1;
+        let j = BigInt::random_bits(bits) / step;
+        let p = match first_prime_in_progression(p0 + j * step, step, bits, stop) {
+            Some(p) => p,
+            None => if stop.load(Ordering::Relaxed) { return None } else { continue },
+        };
+
+        if p.bits() == bits {
+            return Some(StrongPrime { p, r, s, t });
         }
     }
 }
 
 
+// gordon's algorithm, p has exactly `bits` bits
+pub fn generate_strong_prime(bits: usize) -> StrongPrime {
+    if !(128..=1024).contains(&bits) {
+        panic!("Strong prime size must be between 128 and 1024 bits");
+    }
+    parallel_search(move |stop| search_strong_prime(bits, stop))
+}
+
 
-fn primes_64bit() -> u64 {
-    const N: usize = 10000;
-    let start = (N + 1) as u64;
-    let primes = utils::generate_small_primes::<N>();
+// number of bits in p * q, computed without truncating to N chunks
+pub fn product_bits(p: &BigInt, q: &BigInt) -> usize {
+    let mut wide = [0; 2 * bigint::N];
+    bigint::mul_limbs(&p.chunks[..p.size() + 1], &q.chunks[..q.size() + 1], &mut wide);
+    match wide.iter().rposition(|chunk| *chunk != 0) {
+        Some(i) => 64 * i + 64 - wide[i].leading_zeros() as usize,
+        None => 0,
+    }
+}
 
+
+// two distinct `bits` bit primes whose product has exactly 2 * bits bits
+pub fn generate_rsa_primes(bits: usize) -> (BigInt, BigInt) {
+    let generator = PrimeGenerator::new(bits).top_two_bits();
     loop {
-        let num = rng::u64() | 0x8000000000000001u64;
-        let mut result = PrimeResult::Unknown;
+        let p = generator.generate();
+        let q = generator.generate();
+        if p == q {
+            continue;
+        }
+        debug_assert_eq!(product_bits(&p, &q), 2 * bits);
+        return (p, q);
+    }
+}
 
-        for i in 0..N {
-            if num % primes[i] == 0 {
-                result = PrimeResult::Composite;
-                break;
-            }
+
+// p = k * 2^n + 1, proven prime by proth's theorem
+#[derive(Clone, Copy)]
+pub struct ProthPrime {
+    pub p: BigInt,
+    pub k: u64,
+    pub n: usize,
+}
+
+
+fn search_proth_prime(bits: usize, stop: &AtomicBool) -> Option<ProthPrime> {
+    // k gets half of the bits, at most 32, so k < 2^n
+    let k_bits = (bits / 2).min(32);
+    let n = bits - k_bits;
+    let primes = sieving_primes(bits);
+
+    while !stop.load(Ordering::Relaxed) {
+        let k = (rng::u64() >> (64 - k_bits)) | (1 << (k_bits - 1)) | 1;
+        let p = (BigInt::from(k as u128) << n) + BigInt::from(1);
+        if primes.iter().any(|prime| p.rem_u64(*prime) == 0) {
+            continue;
+        }
+        if algos::proth_test(k, n) == PrimeResult::Prime {
+            return Some(ProthPrime { p, k, n });
         }
+    }
+    None
+}
+
+
+// proth prime with exactly `bits` bits
+pub fn generate_proth_prime(bits: usize) -> ProthPrime {
+    if !(16..=1024).contains(&bits) {
+        panic!("Prime size must be between 16 and 1024 bits");
+    }
+    parallel_search(move |stop| search_proth_prime(bits, stop))
+}
+
+
+// p = k * 2^n - 1, proven prime by the lucas-lehmer-riesel test
+#[derive(Clone, Copy)]
+pub struct RieselPrime {
+    pub p: BigInt,
+    pub k: u64,
+    pub n: usize,
+}
+
+
+fn search_riesel_prime(bits: usize, stop: &AtomicBool) -> Option<RieselPrime> {
+    // k gets half of the bits, at most 32, so k < 2^n
+    let k_bits = (bits / 2).min(32);
+    let n = bits - k_bits;
+    let primes = sieving_primes(bits);
 
-        if result == PrimeResult::Unknown {
-            result = algos::trial_division(num, start)
+    while !stop.load(Ordering::Relaxed) {
+        let k = (rng::u64() >> (64 - k_bits)) | (1 << (k_bits - 1)) | 1;
+        let p = (BigInt::from(k as u128) << n) - BigInt::from(1);
+        if primes.iter().any(|prime| p.rem_u64(*prime) == 0) {
+            continue;
         }
+        if algos::llr_test(k, n) == PrimeResult::Prime {
+            return Some(RieselPrime { p, k, n });
+        }
+    }
+    None
+}
 
-        if result == PrimeResult::Prime {
-            return num;
+
+// riesel prime with exactly `bits` bits
+pub fn generate_riesel_prime(bits: usize) -> RieselPrime {
+    if !(16..=1024).contains(&bits) {
+        panic!("Prime size must be between 16 and 1024 bits");
+    }
+    parallel_search(move |stop| search_riesel_prime(bits, stop))
+}
+
+
+fn small_is_prime(n: u64) -> bool {
+    match n {
+        0 | 1 => false,
+        2 | 3 => true,
+        _ => n % 2 == 1 && algos::trial_division(n, 5) == PrimeResult::Prime,
+    }
+}
+
+
+// first prime among the odd numbers start, start +- 2, ... that lies
+// strictly between start and limit, checking start itself too
+fn scan_for_prime(start: BigInt, limit: BigInt, descending: bool) -> Option<BigInt> {
+    let two = BigInt::from(2);
+
+    // candidates that reach the sieve are larger than all of these primes
+    let primes = sieving_primes(64);
+    let mut sieve = Sieve::new(&primes, start, two);
+    if descending {
+        sieve = sieve.reverse();
+    }
+
+    let mut candidate = start;
+    loop {
+        if (descending && candidate <= limit) || (!descending && candidate >= limit) {
+            return None;
         }
+
+        if candidate.bits() <= 32 {
+            if small_is_prime(candidate.chunks[0]) { return Some(candidate); }
+        } else if !sieve.has_factor()
+            && algos::miller_rabin_test(candidate, 10) == PrimeResult::ProbablePrime {
+            return Some(candidate);
+        }
+
+        candidate = if descending { candidate - two } else { candidate + two };
+        sieve.advance();
     }
 }
 
 
+// smallest prime p with n <= p < limit
+fn prime_at_least(n: BigInt, limit: BigInt) -> Option<BigInt> {
+    let two = BigInt::from(2);
+    if n <= two {
+        return if two < limit { Some(two) } else { None };
+    }
+
+    let start = if n.is_even() { n.increase() } else { n };
+    scan_for_prime(start, limit, false)
+}
+
+
+pub fn next_prime(n: &BigInt) -> BigInt {
+    prime_at_least(n.increase(), BigInt::from(1) << 1024)
+        .expect("No prime with at most 1024 bits after n")
+}
+
+
+pub fn prev_prime(n: &BigInt) -> Option<BigInt> {
+    let two = BigInt::from(2);
+    if *n <= two { return None; }
+
+    let start = if n.is_even() { n.decrease() } else { n.decrease().decrease() };
+    scan_for_prime(start, two, true).or(Some(two))
+}
+
+
+// a prime p with lo <= p < hi, starting the search from a random point
+pub fn random_prime_in_range(lo: BigInt, hi: BigInt) -> Option<BigInt> {
+    if hi <= lo { return None; }
+
+    let offset = BigInt::random() % (hi - lo);
+    let start = lo + offset;
+    prime_at_least(start, hi).or_else(|| prime_at_least(lo, start))
+}
+
+
+// uniform in [0, 1)
+fn random_fraction() -> f64 {
+    (rng::u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+
+// maurer's algorithm, appends the pocklington step proving n to `chain`
+fn maurer_prime(bits: usize, chain: &mut CertificateChain) -> BigInt {
+    if bits <= 20 {
+        loop {
+            let n = (rng::u64() >> (64 - bits)) | (1 << (bits - 1)) | 1;
+            if small_is_prime(n) {
+                chain.small_prime = n;
+                return BigInt::from(n as u128);
+            }
+        }
+    }
+
+    // q gets a random fraction of the bits, leaving at least 20 bits for
+    // the cofactor. q also needs more than half of the bits, so q >= sqrt(n)
+    let fraction = if bits > 40 {
+        loop {
+            let fraction = 2f64.powf(random_fraction() - 1.0);
+            if bits as f64 * (1.0 - fraction) > 20.0 { break fraction; }
+        }
+    } else {
+        0.5
+    };
+    let q_bits = ((fraction * bits as f64) as usize + 1).max(bits.div_ceil(2) + 1);
+    let q = maurer_prime(q_bits, chain);
+
+    let one = BigInt::from(1);
+    let two = BigInt::from(2);
+    let three = BigInt::from(3);
+    let primes = sieving_primes(bits);
 
-fn primes_128bit() -> u128 {
+    // n = 2rq + 1 with r in [i + 1, 2i] has exactly `bits` bits
+    let i = (one << (bits - 2)) / q;
     loop {
-        let num = (rng::u64() | 0x8000000000000001u64) as u128;
-        // if algos::fermat_test_u128(num, 1) == PrimeResult::ProbablePrime {
-        //     return num;
-        // }
-        if algos::miller_rabin_test_u128(num, 1) == PrimeResult::ProbablePrime {
-            return num;
+        let r = i + one + BigInt::random() % i;
+        let n = ((r * q) << 1) + one;
+        if primes.iter().any(|prime| n.rem_u64(*prime) == 0) {
+            continue;
+        }
+
+        let witness = two + BigInt::random() % (n - three);
+        let ctx = BarrettCtx::new(n);
+        if ctx.pow_mod(witness, n - one) != one {
+            continue;
+        }
+        let b = ctx.pow_mod(witness, r << 1);
+        if utils::gcd(b - one, n) == one {
+            chain.steps.push(PocklingtonStep { n, q, witness });
+            return n;
         }
     }
+}
+
 
+// provable prime with exactly `bits` bits, together with its certificate
+pub fn generate_maurer_prime(bits: usize) -> (BigInt, CertificateChain) {
+    if !(16..=1024).contains(&bits) {
+        panic!("Prime size must be between 16 and 1024 bits");
+    }
+    let mut chain = CertificateChain { small_prime: 0, steps: Vec::new() };
+    let prime = maurer_prime(bits, &mut chain);
+    (prime, chain)
 }
 
 
-fn primes_1024bit() -> BigInt {
-    const P: usize = 5000;
-    let primes = utils::generate_small_primes::<P>();
+// prime from fips 186-5 appendix a.1.2 together with the values needed to
+// validate it. the certificate repeats the pocklington checks of each step
+#[derive(Clone)]
+pub struct ShaweTaylorPrime {
+    pub prime: BigInt,
+    pub prime_seed: Vec<u8>,
+    pub prime_gen_counter: usize,
+    pub certificate: CertificateChain,
+}
 
-    let zero = BigInt::zero();
-    let mut small_prime = BigInt::zero();
 
-    let mut num = BigInt::random();
-    num.modify();
+// seeds are fixed length bit strings, added to as big endian integers
+fn seed_add(seed: &[u8], amount: u64) -> Vec<u8> {
+    let mut result = seed.to_vec();
+    let mut carry = amount as u128;
+    for byte in result.iter_mut().rev() {
+        if carry == 0 { break; }
+        carry += *byte as u128;
+        *byte = carry as u8;
+        carry >>= 8;
+    }
+    result
+}
 
-    'prime_loop: loop {
-        num = num.increase_by_2();
 
-        for i in 0..P {
-            small_prime.chunks[0] = primes[i];
-            if num % small_prime == zero {
-                continue 'prime_loop;
+fn seed_hash(seed: &[u8], offset: u64) -> [u8; 32] {
+    sha256::sha256(&seed_add(seed, offset))
+}
+
+
+// sum of hash(seed + i) * 2^(256 i) for i = 0..=iterations
+fn seed_hash_sum(seed: &[u8], iterations: usize) -> BigInt {
+    let mut sum = BigInt::zero();
+    for i in 0..=iterations {
+        let mut digest = seed_hash(seed, i as u64);
+        digest.reverse();
+        sum += BigInt::from(&digest[..]) << (256 * i);
+    }
+    sum
+}
+
+
+// st_random_prime, returns the prime, prime_seed and prime_gen_counter
+fn shawe_taylor_prime(
+    length: usize,
+    input_seed: &[u8],
+    chain: &mut CertificateChain,
+) -> Option<(BigInt, Vec<u8>, usize)> {
+    if length < 33 {
+        let mut prime_seed = input_seed.to_vec();
+        let mut prime_gen_counter = 0;
+        loop {
+            let mut c = [0u8; 8];
+            let h0 = seed_hash(&prime_seed, 0);
+            let h1 = seed_hash(&prime_seed, 1);
+            for (i, byte) in c.iter_mut().enumerate() {
+                *byte = h0[24 + i] ^ h1[24 + i];
+            }
+            let c = (1 << (length - 1)) + (u64::from_be_bytes(c) % (1 << (length - 1)));
+            let c = c | 1;
+
+            prime_gen_counter += 1;
+            prime_seed = seed_add(&prime_seed, 2);
+
+            if small_is_prime(c) {
+                chain.small_prime = c;
+                return Some((BigInt::from(c as u128), prime_seed, prime_gen_counter));
+            }
+            if prime_gen_counter > 4 * length {
+                return None;
             }
         }
+    }
+
+    let (c0, mut prime_seed, mut prime_gen_counter) =
+        shawe_taylor_prime(length.div_ceil(2) + 1, input_seed, chain)?;
+
+    let one = BigInt::from(1);
+    let two = BigInt::from(2);
+    let three = BigInt::from(3);
 
-        if algos::miller_rabin_test(num, 10) == PrimeResult::ProbablePrime {
-            return num;
+    let iterations = length.div_ceil(256) - 1;
+    let old_counter = prime_gen_counter;
+
+    let top = one << (length - 1);
+    let x = seed_hash_sum(&prime_seed, iterations);
+    prime_seed = seed_add(&prime_seed, iterations as u64 + 1);
+    let x = top + x % top;
+
+    // c = 2tc0 + 1, the smallest such number >= x
+    let two_c0 = c0 << 1;
+    let mut t = (x + two_c0 - one) / two_c0;
+    loop {
+        if t * two_c0 + one > one << length {
+            t = (top + two_c0 - one) / two_c0;
         }
+        let c = t * two_c0 + one;
+        prime_gen_counter += 1;
+
+        let a = seed_hash_sum(&prime_seed, iterations);
+        prime_seed = seed_add(&prime_seed, iterations as u64 + 1);
+        let a = two + a % (c - three);
+
+        let ctx = BarrettCtx::new(c);
+        let z = ctx.pow_mod(a, t << 1);
+        if !z.is_zero() && utils::gcd(z - one, c) == one && ctx.pow_mod(z, c0) == one {
+            chain.steps.push(PocklingtonStep { n: c, q: c0, witness: a });
+            return Some((c, prime_seed, prime_gen_counter));
+        }
+        if prime_gen_counter >= 4 * length + old_counter {
+            return None;
+        }
+        t = t.increase();
     }
 }
 
 
+// provable prime with exactly `length` bits derived from `input_seed`,
+// None if the construction fails for this seed
+pub fn generate_shawe_taylor_prime(length: usize, input_seed: &[u8]) -> Option<ShaweTaylorPrime> {
+    if !(2..=1024).contains(&length) {
+        panic!("Prime size must be between 2 and 1024 bits");
+    }
+    if input_seed.is_empty() {
+        panic!("Seed must not be empty");
+    }
+
+    let mut certificate = CertificateChain { small_prime: 0, steps: Vec::new() };
+    let (prime, prime_seed, prime_gen_counter) =
+        shawe_taylor_prime(length, input_seed, &mut certificate)?;
+    Some(ShaweTaylorPrime { prime, prime_seed, prime_gen_counter, certificate })
+}
+
+
+// checks the recorded values first, the size of the prime and its
+// certificate chain, then repeats the construction from the seed and
+// compares the outputs
+pub fn validate_shawe_taylor_prime(length: usize, input_seed: &[u8], claimed: &ShaweTaylorPrime) -> bool {
+    if claimed.prime.bits() != length
+        || claimed.certificate.prime() != claimed.prime
+        || !certificate::verify_chain(&claimed.certificate) {
+        return false;
+    }
+    match generate_shawe_taylor_prime(length, input_seed) {
+        Some(expected) => {
+            expected.prime == claimed.prime
+                && expected.prime_seed == claimed.prime_seed
+                && expected.prime_gen_counter == claimed.prime_gen_counter
+        }
+        None => false,
+    }
+}
+
+
+// probable prime from PrimeGenerator together with a proof for it, an
+// elliptic curve certificate or, when ecpp finds no chain of curves, an
+// n - 1 certificate. None if neither method proves the prime, which
+// happens for about 1 in 200 primes at 256 bits, 1 in 15 at 512 bits and
+// 1 in 7 at 1024 bits. a 1024 bit proof takes around 15 seconds in
+// release builds
+pub fn generate_proven_prime(bits: usize) -> Option<(BigInt, Certificate)> {
+    let prime = PrimeGenerator::new(bits).generate();
+    let certificate = ecpp::ecpp_certificate(prime)
+        .or_else(|| algos::pocklington_certificate(prime))?;
+    Some((prime, certificate))
+}
+
+
 pub fn run() {
     // println!("Prime found: {}", primes_16bit());
     // println!("Prime found: {}", primes_64bit());
     // println!("Prime found: {}", primes_128bit());
 
-    let (tx, rx) = std::sync::mpsc::channel();
+    let prime = primes_1024bit();
+    prime.print_decimal();
+}
 
-    for _ in 0..16 {
-        let thread_tx = tx.clone();
-        std::thread::spawn(move || {
-            thread_tx.send(primes_1024bit()).unwrap();
-        });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_probable_prime(n: BigInt) -> bool {
+        !algos::miller_rabin_test(n, 20).is_composite()
     }
 
-    let prime = rx.recv().unwrap();
-    prime.print_decimal();
-    return
+    #[test]
+    fn safe_prime_halves_are_prime() {
+        let one = BigInt::from(1);
+        for bits in [16, 17, 64, 128, 256] {
+            let p = generate_safe_prime(bits);
+            assert_eq!(p.bits(), bits);
+            assert!(is_probable_prime(p));
+            assert!(is_probable_prime((p - one) >> 1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "between 16 and 1024 bits")]
+    fn safe_prime_rejects_narrow_widths() {
+        generate_safe_prime(15);
+    }
+
+    #[test]
+    #[should_panic(expected = "between 16 and 1024 bits")]
+    fn safe_prime_rejects_wide_widths() {
+        generate_safe_prime(1025);
+    }
+
+    #[test]
+    fn congruence_residues() {
+        for (bits, residue, modulus) in [(32, 3, 4), (64, 1, 1 << 16), (64, 2, 3), (128, 5, 3)] {
+            let p = PrimeGenerator::new(bits)
+                .congruence(BigInt::from(residue), BigInt::from(modulus))
+                .generate();
+            assert_eq!(p.bits(), bits);
+            assert_eq!(p.rem_u64(modulus as u64), residue as u64 % modulus as u64);
+            assert!(is_probable_prime(p));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "must be non-zero")]
+    fn congruence_rejects_zero_modulus() {
+        PrimeGenerator::new(64).congruence(BigInt::from(1), BigInt::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "must be coprime")]
+    fn congruence_rejects_shared_factors() {
+        PrimeGenerator::new(64).congruence(BigInt::from(6), BigInt::from(9));
+    }
+
+    #[test]
+    fn rsa_primes() {
+        for bits in [32, 64, 100, 256] {
+            let (p, q) = generate_rsa_primes(bits);
+            assert!(p != q);
+            for prime in [p, q] {
+                assert_eq!(prime.bits(), bits);
+                assert!(prime.bit(bits - 1) && prime.bit(bits - 2));
+                assert!(is_probable_prime(prime));
+            }
+            assert_eq!(product_bits(&p, &q), 2 * bits);
+            assert_eq!((p * q).bits(), 2 * bits);
+        }
+    }
+
+    #[test]
+    fn top_two_bits_products() {
+        for bits in [16, 64, 65, 300] {
+            let p = PrimeGenerator::new(bits).top_two_bits().generate();
+            assert!(p.bit(bits - 1) && p.bit(bits - 2));
+        }
+
+        let one = BigInt::from(1);
+        for k in [2, 32, 63, 64, 65, 512, 1024] {
+            let power = one << k;
+            let half = one << (k - 1);
+            assert_eq!(product_bits(&power.decrease(), &power.decrease()), 2 * k);
+            assert_eq!(product_bits(&power, &power), 2 * k + 1);
+            assert_eq!(product_bits(&power, &power.decrease()), 2 * k);
+            assert_eq!(product_bits(&half, &half), 2 * k - 1);
+            assert_eq!(product_bits(&half, &power.decrease()), 2 * k - 1);
+        }
+        assert_eq!(product_bits(&BigInt::zero(), &(one << 100)), 0);
+
+        // the top two bits alone keep the product at 2k bits, a single
+        // top bit does not
+        let k = 128;
+        let top_two = (one << (k - 1)) + (one << (k - 2));
+        let top_one = (one << (k - 1)) + (one << (k - 3));
+        assert_eq!(product_bits(&top_two, &top_two), 2 * k);
+        assert_eq!(product_bits(&top_one, &top_one), 2 * k - 1);
+    }
+
+    #[test]
+    fn next_and_prev_prime() {
+        let small = |n: u128| BigInt::from(n);
+        let below = |bits: usize, k: u128| (small(1) << bits) - small(k);
+        let above = |bits: usize, k: u128| (small(1) << bits) + small(k);
+
+        for (n, next) in [(0, 2), (1, 2), (2, 3), (3, 5), (4, 5), (89, 97)] {
+            assert_eq!(next_prime(&small(n)), small(next));
+        }
+        for n in [0, 1, 2] {
+            assert!(prev_prime(&small(n)).is_none());
+        }
+        for (n, prev) in [(3, 2), (4, 3), (5, 3), (97, 89)] {
+            assert_eq!(prev_prime(&small(n)), Some(small(prev)));
+        }
+
+        // 2^64 - 59 and 2^64 + 13 are the primes around 2^64, and the
+        // primes around 2^127 - 1 are 2^127 - 25 and 2^127 + 29
+        assert_eq!(next_prime(&below(64, 59)), above(64, 13));
+        assert_eq!(next_prime(&above(64, 0)), above(64, 13));
+        assert_eq!(prev_prime(&above(64, 13)), Some(below(64, 59)));
+        assert_eq!(prev_prime(&above(64, 0)), Some(below(64, 59)));
+        assert_eq!(next_prime(&below(127, 25)), below(127, 1));
+        assert_eq!(next_prime(&below(127, 1)), above(127, 29));
+        assert_eq!(prev_prime(&below(127, 1)), Some(below(127, 25)));
+        assert_eq!(prev_prime(&above(127, 29)), Some(below(127, 1)));
+    }
+
+    #[test]
+    fn random_prime_in_range_bounds() {
+        let small = |n: u128| BigInt::from(n);
+        assert!(random_prime_in_range(small(30), small(30)).is_none());
+        assert!(random_prime_in_range(small(30), small(20)).is_none());
+        assert!(random_prime_in_range(small(24), small(29)).is_none());
+        for _ in 0..20 {
+            assert_eq!(random_prime_in_range(small(2), small(3)), Some(small(2)));
+            assert_eq!(random_prime_in_range(small(23), small(24)), Some(small(23)));
+            assert_eq!(random_prime_in_range(small(24), small(30)), Some(small(29)));
+        }
+
+        // the only primes in [2^64 - 59, 2^64 + 14) are its two ends
+        let lo = (small(1) << 64) - small(59);
+        let hi = (small(1) << 64) + small(14);
+        for _ in 0..20 {
+            let p = random_prime_in_range(lo, hi).unwrap();
+            assert!(p == lo || p == hi.decrease());
+        }
+
+        for _ in 0..20 {
+            let lo = BigInt::random_bits(200);
+            let hi = lo + BigInt::random_bits(40);
+            let p = random_prime_in_range(lo, hi).unwrap();
+            assert!(lo <= p && p < hi && is_probable_prime(p));
+        }
+    }
+
+    #[test]
+    fn generated_prime_widths() {
+        for bits in [16, 17, 32] {
+            let p: u64 = generate_prime(bits);
+            assert_eq!(64 - p.leading_zeros() as usize, bits);
+            assert!(small_is_prime(p));
+        }
+        let p: u16 = generate_prime(16);
+        assert!(p >> 15 == 1 && small_is_prime(p as u64));
+        let p: u128 = generate_prime(128);
+        assert!(p >> 127 == 1 && is_probable_prime(BigInt::from(p)));
+        let p: BigInt = generate_prime(300);
+        assert!(p.bits() == 300 && is_probable_prime(p));
+    }
+
+    #[test]
+    fn prime_int_perfect_powers() {
+        assert!(PrimeInt::is_perfect_power(3u16.pow(10)));
+        assert!(PrimeInt::is_perfect_power(65521u64 * 65521));
+        assert!(PrimeInt::is_perfect_power(7u128.pow(45)));
+        let p = BigInt::from(1000003);
+        assert!(PrimeInt::is_perfect_power(p.square() * p));
+        assert!(!PrimeInt::is_perfect_power(65521u64 * 65519));
+        assert!(!PrimeInt::is_perfect_power(p.square() * BigInt::from(1000033)));
+    }
+
+    #[test]
+    fn strong_prime_factors() {
+        let one = BigInt::from(1);
+        for bits in [128, 256] {
+            let StrongPrime { p, r, s, t } = generate_strong_prime(bits);
+            assert_eq!(p.bits(), bits);
+            for prime in [p, r, s, t] {
+                assert!(is_probable_prime(prime));
+            }
+            assert!(((p - one) % r).is_zero());
+            assert!(((p + one) % s).is_zero());
+            assert!(((r - one) % t).is_zero());
+            assert_eq!(s.bits(), bits / 2 - 16);
+            assert_eq!(t.bits(), bits / 2 - 32);
+            assert!(r.bits() >= bits / 2 - 16 && r.bits() <= bits / 2);
+        }
+    }
+
+    #[test]
+    fn maurer_round_trip() {
+        let two = BigInt::from(2);
+        for bits in [16, 64, 256, 512] {
+            let (prime, chain) = generate_maurer_prime(bits);
+            assert_eq!(prime.bits(), bits);
+            assert_eq!(chain.prime(), prime);
+            assert!(certificate::verify_chain(&chain));
+            assert!(certificate::verify_certificate(&chain.to_certificate()));
+            if bits <= 20 {
+                continue;
+            }
+
+            let last = chain.steps.len() - 1;
+            let mut tampered = chain.clone();
+            tampered.steps[last].n += two;
+            assert!(!certificate::verify_chain(&tampered));
+            let mut tampered = chain.clone();
+            tampered.steps[last].witness = BigInt::from(1);
+            assert!(!certificate::verify_chain(&tampered));
+            let mut tampered = chain.clone();
+            tampered.steps[0].q += two;
+            assert!(!certificate::verify_chain(&tampered));
+            let mut tampered = chain.clone();
+            tampered.small_prime += 2;
+            assert!(!certificate::verify_chain(&tampered));
+            if last > 0 {
+                let mut tampered = chain.clone();
+                tampered.steps.remove(0);
+                assert!(!certificate::verify_chain(&tampered));
+            }
+        }
+    }
+
+    #[test]
+    fn proven_prime_certificate() {
+        for bits in [48, 96, 160] {
+            // a prime may go unproven, but not three in a row
+            let proofs: Vec<_> = (0..3).filter_map(|_| generate_proven_prime(bits)).collect();
+            assert!(!proofs.is_empty());
+            for (prime, certificate) in proofs {
+                assert_eq!(prime.bits(), bits);
+                assert_eq!(certificate.prime(), prime);
+                assert!(certificate::verify_certificate(&certificate));
+            }
+        }
+    }
+
+    #[test]
+    fn proth_prime_form() {
+        for bits in [16, 40, 64, 200] {
+            let ProthPrime { p, k, n } = generate_proth_prime(bits);
+            assert_eq!(p.bits(), bits);
+            assert!(n >= 64 || k >> n == 0);
+            assert_eq!(p, (BigInt::from(k as u128) << n) + BigInt::from(1));
+            assert!(is_probable_prime(p));
+        }
+    }
+
+    #[test]
+    fn shawe_taylor_round_trip() {
+        let seed = [0x5a; 32];
+        for length in [20, 64, 300] {
+            let prime = generate_shawe_taylor_prime(length, &seed).unwrap();
+            assert_eq!(prime.prime.bits(), length);
+            assert!(validate_shawe_taylor_prime(length, &seed, &prime));
+            assert!(!validate_shawe_taylor_prime(length, &[0x5b; 32], &prime));
+
+            let mut tampered = prime.clone();
+            tampered.prime_gen_counter += 1;
+            assert!(!validate_shawe_taylor_prime(length, &seed, &tampered));
+            let mut tampered = prime.clone();
+            tampered.prime_seed[0] ^= 1;
+            assert!(!validate_shawe_taylor_prime(length, &seed, &tampered));
+            let mut tampered = prime.clone();
+            tampered.certificate.small_prime += 2;
+            assert!(!validate_shawe_taylor_prime(length, &seed, &tampered));
+        }
+    }
 }
@@ -0,0 +1,675 @@
+use std::ops::{Add, Div, Rem, Shr, Sub};
+
+use crate::rng;
+use crate::utils;
+use crate::algos::{Evidence, PrimeResult};
+use crate::barrett::BarrettCtx;
+use crate::bigint::{BigInt, N};
+use crate::sieve::sieving_primes;
+
+
+
+// the operations the generic tests need from an integer type. arithmetic
+// modulo a fixed n goes through a context that is set up once per number
+pub trait Integer:
+    Copy + PartialEq + PartialOrd
+    + Add<Output = Self> + Sub<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+    + Shr<usize, Output = Self>
+{
+    type Ctx;
+
+    fn from_u64(n: u64) -> Self;
+    fn to_bigint(self) -> BigInt;
+    fn bits(self) -> usize;
+    fn bit(self, i: usize) -> bool;
+    fn trailing_zeros(self) -> usize;
+    fn rem_u64(self, modulus: u64) -> u64;
+    fn is_perfect_square(self) -> bool;
+    fn isqrt(self) -> Self;
+    fn gcd(self, other: Self) -> Self;
+
+    // random number below self
+    fn random_below(self) -> Self;
+
+    fn ctx(self) -> Self::Ctx;
+    fn mul_mod(ctx: &Self::Ctx, a: Self, b: Self) -> Self;
+
+    fn square_mod(ctx: &Self::Ctx, a: Self) -> Self {
+        Self::mul_mod(ctx, a, a)
+    }
+
+    fn pow_mod(ctx: &Self::Ctx, base: Self, exponent: Self) -> Self {
+        let mut result = Self::from_u64(1);
+        for i in (0..exponent.bits()).rev() {
+            result = Self::square_mod(ctx, result);
+            if exponent.bit(i) {
+                result = Self::mul_mod(ctx, result, base);
+            }
+        }
+        result
+    }
+}
+
+
+impl Integer for u16 {
+    type Ctx = u16;
+
+    fn from_u64(n: u64) -> Self { n as u16 }
+    fn to_bigint(self) -> BigInt { BigInt::from(self as u128) }
+    fn bits(self) -> usize { 16 - self.leading_zeros() as usize }
+    fn bit(self, i: usize) -> bool { (self >> i) & 1 == 1 }
+    fn trailing_zeros(self) -> usize { u16::trailing_zeros(self) as usize }
+    fn rem_u64(self, modulus: u64) -> u64 { self as u64 % modulus }
+    fn is_perfect_square(self) -> bool { utils::is_perfect_square_u128(self as u128) }
+    fn isqrt(self) -> Self { utils::isqrt_u64(self as u64) as u16 }
+    fn gcd(self, other: Self) -> Self { utils::gcd_u64(self as u64, other as u64) as u16 }
+    fn random_below(self) -> Self { rng::u16() % self }
+
+    fn ctx(self) -> u16 { self }
+    fn mul_mod(ctx: &u16, a: Self, b: Self) -> Self {
+        ((a as u32 * b as u32) % *ctx as u32) as u16
+    }
+}
+
+
+impl Integer for u64 {
+    type Ctx = u64;
+
+    fn from_u64(n: u64) -> Self { n }
+    fn to_bigint(self) -> BigInt { BigInt::from(self as u128) }
+    fn bits(self) -> usize { 64 - self.leading_zeros() as usize }
+    fn bit(self, i: usize) -> bool { (self >> i) & 1 == 1 }
+    fn trailing_zeros(self) -> usize { u64::trailing_zeros(self) as usize }
+    fn rem_u64(self, modulus: u64) -> u64 { self % modulus }
+    fn is_perfect_square(self) -> bool { utils::is_perfect_square_u128(self as u128) }
+    fn isqrt(self) -> Self { utils::isqrt_u64(self) }
+    fn gcd(self, other: Self) -> Self { utils::gcd_u64(self, other) }
+    fn random_below(self) -> Self { rng::u64() % self }
+
+    fn ctx(self) -> u64 { self }
+    fn mul_mod(ctx: &u64, a: Self, b: Self) -> Self {
+        ((a as u128 * b as u128) % *ctx as u128) as u64
+    }
+}
+
+
+impl Integer for u128 {
+    type Ctx = u128;
+
+    fn from_u64(n: u64) -> Self { n as u128 }
+    fn to_bigint(self) -> BigInt { BigInt::from(self) }
+    fn bits(self) -> usize { 128 - self.leading_zeros() as usize }
+    fn bit(self, i: usize) -> bool { (self >> i) & 1 == 1 }
+    fn trailing_zeros(self) -> usize { u128::trailing_zeros(self) as usize }
+    fn rem_u64(self, modulus: u64) -> u64 { (self % modulus as u128) as u64 }
+    fn is_perfect_square(self) -> bool { utils::is_perfect_square_u128(self) }
+    fn isqrt(self) -> Self { utils::isqrt_u128(self) }
+    fn gcd(self, other: Self) -> Self { utils::gcd_u128(self, other) }
+    fn random_below(self) -> Self { rng::u128() % self }
+
+    fn ctx(self) -> u128 { self }
+    fn mul_mod(ctx: &u128, a: Self, b: Self) -> Self {
+        utils::mul_mod_u128(a, b, *ctx)
+    }
+}
+
+
+// assumption: n has at most 1024 bits, like the barrett reduction
+impl Integer for BigInt {
+    type Ctx = BarrettCtx;
+
+    fn from_u64(n: u64) -> Self { BigInt::from(n as u128) }
+    fn to_bigint(self) -> BigInt { self }
+    fn bits(self) -> usize { BigInt::bits(&self) }
+    fn bit(self, i: usize) -> bool { BigInt::bit(&self, i) }
+    fn trailing_zeros(self) -> usize { BigInt::trailing_zeros(&self) }
+    fn rem_u64(self, modulus: u64) -> u64 { BigInt::rem_u64(&self, modulus) }
+    fn is_perfect_square(self) -> bool { utils::is_perfect_square(self) }
+    fn isqrt(self) -> Self { utils::isqrt(self) }
+    fn gcd(self, other: Self) -> Self { utils::gcd(self, other) }
+    fn random_below(self) -> Self { BigInt::random() % self }
+
+    fn ctx(self) -> BarrettCtx { BarrettCtx::new(self) }
+    fn mul_mod(ctx: &BarrettCtx, a: Self, b: Self) -> Self { ctx.mul_mod(a, b) }
+    fn square_mod(ctx: &BarrettCtx, a: Self) -> Self { ctx.square_mod(a) }
+    fn pow_mod(ctx: &BarrettCtx, base: Self, exponent: Self) -> Self { ctx.pow_mod(base, exponent) }
+}
+
+
+
+// the integer types the generic prime generator works with
+pub trait PrimeInt: Integer + Send + Sync + 'static {
+    // widest prime that can be generated
+    const BITS: usize;
+
+    // random odd number with exactly `bits` bits
+    fn random_odd(bits: usize) -> Self;
+
+    // 2^bits - 1
+    fn ones(bits: usize) -> Self;
+
+    // n = a^k for some k > 1
+    fn is_perfect_power(self) -> bool;
+}
+
+
+impl PrimeInt for u16 {
+    const BITS: usize = 16;
+
+    fn random_odd(bits: usize) -> Self { (rng::u16() >> (16 - bits)) | (1 << (bits - 1)) | 1 }
+    fn ones(bits: usize) -> Self { u16::MAX >> (16 - bits) }
+    fn is_perfect_power(self) -> bool { utils::is_perfect_power_u128(self as u128) }
+}
+
+
+impl PrimeInt for u64 {
+    const BITS: usize = 64;
+
+    fn random_odd(bits: usize) -> Self { (rng::u64() >> (64 - bits)) | (1 << (bits - 1)) | 1 }
+    fn ones(bits: usize) -> Self { u64::MAX >> (64 - bits) }
+    fn is_perfect_power(self) -> bool { utils::is_perfect_power_u128(self as u128) }
+}
+
+
+impl PrimeInt for u128 {
+    const BITS: usize = 128;
+
+    fn random_odd(bits: usize) -> Self { (rng::u128() >> (128 - bits)) | (1 << (bits - 1)) | 1 }
+    fn ones(bits: usize) -> Self { u128::MAX >> (128 - bits) }
+    fn is_perfect_power(self) -> bool { utils::is_perfect_power_u128(self) }
+}
+
+
+impl PrimeInt for BigInt {
+    const BITS: usize = 1024;
+
+    fn random_odd(bits: usize) -> Self { BigInt::random_bits(bits) }
+    fn ones(bits: usize) -> Self { (BigInt::from(1) << bits) - BigInt::from(1) }
+    fn is_perfect_power(self) -> bool { utils::is_perfect_power(self) }
+}
+
+
+
+pub fn add_mod<T: Integer>(a: T, b: T, n: T) -> T {
+    if a >= n - b { a - (n - b) } else { a + b }
+}
+
+
+pub fn sub_mod<T: Integer>(a: T, b: T, n: T) -> T {
+    if a >= b { a - b } else { a + (n - b) }
+}
+
+
+// x / 2 mod n for odd n, (x + n) / 2 is formed without overflowing
+fn half_mod<T: Integer>(x: T, n: T) -> T {
+    if x.bit(0) { (x >> 1) + (n >> 1) + T::from_u64(1) } else { x >> 1 }
+}
+
+
+// small signed x mod n
+fn from_i64<T: Integer>(x: i64, n: T) -> T {
+    let value = T::from_u64(x.unsigned_abs()) % n;
+    if x < 0 && value != T::from_u64(0) { n - value } else { value }
+}
+
+
+// jacobi symbol (d / n) for a small d and odd n, by reciprocity
+fn jacobi_small<T: Integer>(d: i64, n: T) -> i8 {
+    let n_mod_8 = n.rem_u64(8);
+    let mut result = if d < 0 && n_mod_8 % 4 == 3 { -1 } else { 1 };
+
+    let mut m = d.unsigned_abs();
+    if m == 0 {
+        return if n == T::from_u64(1) { 1 } else { 0 };
+    }
+    let zeros = m.trailing_zeros();
+    m >>= zeros;
+    if zeros % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+        result = -result;
+    }
+    if m % 4 == 3 && n_mod_8 % 4 == 3 {
+        result = -result;
+    }
+    result * utils::jacobi_u64(n.rem_u64(m), m)
+}
+
+
+// None if n is a strong probable prime to the base, otherwise the evidence
+// against it. n odd and base < n
+fn strong_witness<T: Integer>(ctx: &T::Ctx, n: T, base: T) -> Option<Evidence> {
+    let one = T::from_u64(1);
+    let n_minus_1 = n - one;
+    let s = n_minus_1.trailing_zeros();
+
+    let mut x = T::pow_mod(ctx, base, n_minus_1 >> s);
+    if x == one || x == n_minus_1 {
+        return None;
+    }
+    for _ in 1..s {
+        let square = T::square_mod(ctx, x);
+        if square == n_minus_1 {
+            return None;
+        }
+        if square == one {
+            return Some(Evidence::SquareRoot(x.to_bigint()));
+        }
+        x = square;
+    }
+
+    // x = a^((n-1)/2) != -1
+    if T::square_mod(ctx, x) == one {
+        Some(Evidence::SquareRoot(x.to_bigint()))
+    } else {
+        Some(Evidence::FermatWitness(base.to_bigint()))
+    }
+}
+
+
+// the cases every test handles the same way, None once n is odd and > 3
+fn small_cases<T: Integer>(n: T) -> Option<PrimeResult> {
+    if n < T::from_u64(2) {
+        Some(PrimeResult::Composite(Evidence::BelowTwo))
+    } else if n < T::from_u64(4) {
+        Some(PrimeResult::Prime)
+    } else if !n.bit(0) {
+        Some(PrimeResult::Composite(Evidence::Factor(BigInt::from(2))))
+    } else {
+        None
+    }
+}
+
+
+
+pub trait PrimalityTest<T: Integer> {
+    fn test(&self, n: T) -> PrimeResult;
+}
+
+
+pub struct Fermat {
+    rounds: usize,
+}
+
+
+impl Fermat {
+    pub fn new(rounds: usize) -> Self {
+        Self { rounds }
+    }
+}
+
+
+impl<T: Integer> PrimalityTest<T> for Fermat {
+    fn test(&self, n: T) -> PrimeResult {
+        if let Some(result) = small_cases(n) {
+            return result;
+        }
+        let one = T::from_u64(1);
+        let ctx = n.ctx();
+
+        for _ in 0..self.rounds {
+            let base = (n - T::from_u64(3)).random_below() + T::from_u64(2);
+            if T::pow_mod(&ctx, base, n - one) != one {
+                return PrimeResult::Composite(Evidence::FermatWitness(base.to_bigint()));
+            }
+        }
+        PrimeResult::ProbablePrime
+    }
+}
+
+
+pub struct MillerRabin {
+    rounds: usize,
+}
+
+
+impl MillerRabin {
+    pub fn new(rounds: usize) -> Self {
+        Self { rounds }
+    }
+}
+
+
+impl<T: Integer> PrimalityTest<T> for MillerRabin {
+    fn test(&self, n: T) -> PrimeResult {
+        if let Some(result) = small_cases(n) {
+            return result;
+        }
+        let ctx = n.ctx();
+
+        for _ in 0..self.rounds {
+            let base = (n - T::from_u64(3)).random_below() + T::from_u64(2);
+            if let Some(evidence) = strong_witness(&ctx, n, base) {
+                return PrimeResult::Composite(evidence);
+            }
+        }
+        PrimeResult::ProbablePrime
+    }
+}
+
+
+// division by the sieving primes, Unknown if n has no factor among them but
+// is too large to be proven prime that way
+pub struct TrialDivision;
+
+
+impl<T: Integer> PrimalityTest<T> for TrialDivision {
+    fn test(&self, n: T) -> PrimeResult {
+        if let Some(result) = small_cases(n) {
+            return result;
+        }
+        // comparing p with the root keeps p^2 from overflowing a u16
+        let root = n.isqrt();
+        for p in sieving_primes(64 * N) {
+            if T::from_u64(p) > root {
+                return PrimeResult::Prime;
+            }
+            if n.rem_u64(p) == 0 {
+                return PrimeResult::Composite(Evidence::Factor(BigInt::from(p as u128)));
+            }
+        }
+        PrimeResult::Unknown
+    }
+}
+
+
+// baillie-psw: a strong probable prime test to base 2 followed by a strong
+// lucas test with selfridge's parameters
+pub struct Bpsw;
+
+
+impl Bpsw {
+
+    // strong lucas probable prime with P = 1, Q = (1 - D) / 4 and D the first
+    // of 5, -7, 9, -11, ... with (D / n) = -1
+    fn strong_lucas<T: Integer>(ctx: &T::Ctx, n: T) -> PrimeResult {
+        let zero = T::from_u64(0);
+
+        // squares have no such D
+        if n.is_perfect_square() {
+            return PrimeResult::Composite(Evidence::Factor(utils::isqrt(n.to_bigint())));
+        }
+        let mut d = 5i64;
+        loop {
+            let m = d.unsigned_abs();
+            match jacobi_small(d, n) {
+                -1 => break,
+                0 if T::from_u64(m) != n => {
+                    let factor = utils::gcd_u64(n.rem_u64(m), m);
+                    return PrimeResult::Composite(Evidence::Factor(BigInt::from(factor as u128)));
+                },
+                _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+            }
+        }
+        let q = from_i64((1 - d) / 4, n);
+        let big_d = from_i64(d, n);
+
+        // U_k, V_k and Q^k for the prefixes k of the odd part of n + 1
+        let n_plus_1 = n + T::from_u64(1);
+        let s = n_plus_1.trailing_zeros();
+        let k = n_plus_1 >> s;
+        let mut u = T::from_u64(1);
+        let mut v = T::from_u64(1);
+        let mut q_k = q;
+        for i in (0..(k.bits() - 1)).rev() {
+            // U_2k = U_k V_k, V_2k = V_k^2 - 2 Q^k
+            u = T::mul_mod(ctx, u, v);
+            v = sub_mod(T::square_mod(ctx, v), add_mod(q_k, q_k, n), n);
+            q_k = T::square_mod(ctx, q_k);
+            if k.bit(i) {
+                // U_k+1 = (U_k + V_k) / 2, V_k+1 = (D U_k + V_k) / 2
+                let next_u = half_mod(add_mod(u, v, n), n);
+                v = half_mod(add_mod(T::mul_mod(ctx, big_d, u), v, n), n);
+                u = next_u;
+                q_k = T::mul_mod(ctx, q_k, q);
+            }
+        }
+
+        if u == zero || v == zero {
+            return PrimeResult::ProbablePrime;
+        }
+        for _ in 1..s {
+            v = sub_mod(T::square_mod(ctx, v), add_mod(q_k, q_k, n), n);
+            q_k = T::square_mod(ctx, q_k);
+            if v == zero {
+                return PrimeResult::ProbablePrime;
+            }
+        }
+        PrimeResult::Composite(Evidence::Sequence)
+    }
+
+}
+
+
+impl<T: Integer> PrimalityTest<T> for Bpsw {
+    fn test(&self, n: T) -> PrimeResult {
+        if let Some(result) = small_cases(n) {
+            return result;
+        }
+        // the lucas part needs n > |D| for the few small D
+        for p in [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+            if n == T::from_u64(p) {
+                return PrimeResult::Prime;
+            }
+            if n.rem_u64(p) == 0 {
+                return PrimeResult::Composite(Evidence::Factor(BigInt::from(p as u128)));
+            }
+        }
+        // one reduction context for both halves
+        let ctx = n.ctx();
+        if let Some(evidence) = strong_witness(&ctx, n, T::from_u64(2)) {
+            return PrimeResult::Composite(evidence);
+        }
+        Self::strong_lucas(&ctx, n)
+    }
+}
+
+
+
+// runs the tests in order until one of them is conclusive, ProbablePrime
+// if any of them said so and Unknown otherwise
+pub struct Pipeline<T: Integer> {
+    stages: Vec<Box<dyn PrimalityTest<T> + Send + Sync>>,
+}
+
+
+impl<T: Integer> Default for Pipeline<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+impl<T: Integer> Pipeline<T> {
+
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    pub fn then(mut self, stage: impl PrimalityTest<T> + Send + Sync + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+}
+
+
+impl<T: Integer> PrimalityTest<T> for Pipeline<T> {
+    fn test(&self, n: T) -> PrimeResult {
+        let mut result = PrimeResult::Unknown;
+        for stage in self.stages.iter() {
+            match stage.test(n) {
+                PrimeResult::ProbablePrime => result = PrimeResult::ProbablePrime,
+                PrimeResult::Unknown => {},
+                conclusive => return conclusive,
+            }
+        }
+        result
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn is_prime(n: u64) -> bool {
+        n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+    }
+
+    // strong pseudoprimes to base 2, the second one also to bases 3, 5 and 7
+    const STRONG_PSEUDOPRIMES: [u64; 2] = [2047, 3215031751];
+    const CARMICHAEL: [u64; 2] = [561, 41041];
+    // every strong lucas pseudoprime below 10^5 with selfridge's parameters
+    const STRONG_LUCAS_PSEUDOPRIMES: [u64; 12] = [
+        5459, 5777, 10877, 16109, 18971, 22499, 24569, 25199, 40309, 58519, 75077, 97439,
+    ];
+
+    // the result of every test type on n, as u64, u128 and BigInt
+    fn all_widths<P>(test: &P, n: u64) -> [PrimeResult; 3]
+    where P: PrimalityTest<u64> + PrimalityTest<u128> + PrimalityTest<BigInt> {
+        [test.test(n), test.test(n as u128), test.test(BigInt::from(n as u128))]
+    }
+
+    #[test]
+    fn exhaustive_u16() {
+        for n in 0..=u16::MAX {
+            let prime = is_prime(n as u64);
+            for result in [Bpsw.test(n), TrialDivision.test(n), MillerRabin::new(20).test(n)] {
+                match prime {
+                    true => assert!(matches!(result, PrimeResult::Prime | PrimeResult::ProbablePrime), "{}", n),
+                    false => assert!(result.is_composite(), "{}", n),
+                }
+            }
+            // trial division is exact below the square of its largest prime
+            assert_eq!(TrialDivision.test(n) == PrimeResult::Prime, prime, "{}", n);
+        }
+    }
+
+    #[test]
+    fn pseudoprimes_are_composite() {
+        for n in STRONG_PSEUDOPRIMES.into_iter().chain(CARMICHAEL).chain(STRONG_LUCAS_PSEUDOPRIMES) {
+            for result in all_widths(&Bpsw, n) {
+                assert!(result.is_composite(), "{}", n);
+            }
+            for result in all_widths(&MillerRabin::new(20), n) {
+                assert!(result.is_composite(), "{}", n);
+            }
+            // a carmichael number only fails for bases sharing a factor with
+            // it. 3215031751 = 151 * 751 * 28351 is one, and too few bases
+            // share a factor with it for the fermat test to notice
+            if n != 3215031751 {
+                for result in all_widths(&Fermat::new(64), n) {
+                    assert!(result.is_composite(), "{}", n);
+                }
+            }
+            for result in all_widths(&TrialDivision, n) {
+                assert!(matches!(result, PrimeResult::Composite(Evidence::Factor(_))), "{}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn fermat_and_strong_bases() {
+        for n in STRONG_PSEUDOPRIMES {
+            assert!(strong_witness::<u64>(&n, n, 2).is_none());
+        }
+        assert!(strong_witness::<u64>(&3215031751u64, 3215031751, 11).is_some());
+        for n in CARMICHAEL {
+            for base in [2, 5, 19, 23] {
+                assert_eq!(u64::pow_mod(&n, base, n - 1), 1);
+            }
+            assert!(strong_witness::<u64>(&n, n, 2).is_some());
+        }
+    }
+
+    #[test]
+    fn strong_lucas_pseudoprimes() {
+        for n in (5..100_000u64).step_by(2) {
+            if n.is_perfect_square() {
+                continue;
+            }
+            let probable = Bpsw::strong_lucas::<u64>(&n, n) == PrimeResult::ProbablePrime;
+            let expected = is_prime(n) || STRONG_LUCAS_PSEUDOPRIMES.contains(&n);
+            assert_eq!(probable, expected, "{}", n);
+        }
+    }
+
+    #[test]
+    fn large_primes() {
+        let one = BigInt::from(1);
+        for p in [61, 89, 127, 521, 607] {
+            let prime = (one << p) - one;
+            assert_eq!(Bpsw.test(prime), PrimeResult::ProbablePrime);
+            assert_eq!(MillerRabin::new(10).test(prime), PrimeResult::ProbablePrime);
+            assert_eq!(Fermat::new(10).test(prime), PrimeResult::ProbablePrime);
+            assert_eq!(TrialDivision.test(prime), PrimeResult::Unknown);
+        }
+        let prime = (1u128 << 127) - 1;
+        assert_eq!(Bpsw.test(prime), PrimeResult::ProbablePrime);
+        assert!(Bpsw.test(prime - 2).is_composite());
+        assert_eq!(Bpsw.test(u64::MAX - 58), PrimeResult::ProbablePrime);
+    }
+
+    type Answer = fn() -> PrimeResult;
+
+    // a stage with a fixed answer that counts how often it ran
+    struct Fixed {
+        result: Answer,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl PrimalityTest<u64> for Fixed {
+        fn test(&self, _: u64) -> PrimeResult {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            (self.result)()
+        }
+    }
+
+    fn stages(results: &[Answer]) -> (Pipeline<u64>, Vec<Arc<AtomicUsize>>) {
+        let mut pipeline = Pipeline::new();
+        let mut calls = Vec::new();
+        for result in results {
+            let counter = Arc::new(AtomicUsize::new(0));
+            pipeline = pipeline.then(Fixed { result: *result, calls: counter.clone() });
+            calls.push(counter);
+        }
+        (pipeline, calls)
+    }
+
+    #[test]
+    fn pipeline_stops_at_first_conclusive_stage() {
+        let unknown = || PrimeResult::Unknown;
+        let probable = || PrimeResult::ProbablePrime;
+        let prime = || PrimeResult::Prime;
+        let composite = || PrimeResult::Composite(Evidence::Sequence);
+
+        let cases: [(&[Answer], PrimeResult, usize); 6] = [
+            (&[], PrimeResult::Unknown, 0),
+            (&[unknown, unknown], PrimeResult::Unknown, 2),
+            (&[unknown, probable, unknown], PrimeResult::ProbablePrime, 3),
+            (&[probable, composite, prime], PrimeResult::Composite(Evidence::Sequence), 2),
+            (&[unknown, prime, composite], PrimeResult::Prime, 2),
+            (&[composite, prime], PrimeResult::Composite(Evidence::Sequence), 1),
+        ];
+        for (results, expected, ran) in cases {
+            let (pipeline, calls) = stages(results);
+            assert_eq!(pipeline.test(1000003), expected);
+            for (i, counter) in calls.iter().enumerate() {
+                assert_eq!(counter.load(Ordering::Relaxed), (i < ran) as usize);
+            }
+        }
+
+        // trial division settles small factors before bpsw runs
+        let counter = Arc::new(AtomicUsize::new(0));
+        let pipeline = Pipeline::new()
+            .then(TrialDivision)
+            .then(Fixed { result: probable, calls: counter.clone() });
+        assert_eq!(pipeline.test(3 * 1000003), PrimeResult::Composite(Evidence::Factor(BigInt::from(3))));
+        assert_eq!(pipeline.test(1000003), PrimeResult::Prime);
+        assert_eq!(counter.load(Ordering::Relaxed), 0);
+        assert_eq!(pipeline.test((1 << 61) - 1), PrimeResult::ProbablePrime);
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+    }
+}
@@ -3,8 +3,8 @@ use std::io::Read;
 use std::fs::File;
 
 
-pub fn insert_random_bytes(mut bytes: &mut[u8]) -> std::io::Result<()> {
-    File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+pub fn insert_random_bytes(bytes: &mut[u8]) -> std::io::Result<()> {
+    File::open("/dev/urandom")?.read_exact(bytes)?;
     Ok(())
 }
 
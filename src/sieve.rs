@@ -0,0 +1,151 @@
+use std::sync::OnceLock;
+
+use crate::utils;
+use crate::primality::Integer;
+
+
+// tracks the residues of an arithmetic progression start, start + step, ...
+// modulo a list of small primes, so each candidate can be checked against
+// all of them with u64 arithmetic instead of BigInt divisions
+pub struct Sieve {
+    pub primes: Vec<u64>,
+    residues: Vec<u64>,
+    steps: Vec<u64>,
+}
+
+
+impl Sieve {
+
+    pub fn new<T: Integer>(primes: &[u64], start: T, step: T) -> Self {
+        Self {
+            primes: primes.to_vec(),
+            residues: primes.iter().map(|p| start.rem_u64(*p)).collect(),
+            steps: primes.iter().map(|p| step.rem_u64(*p)).collect(),
+        }
+    }
+
+    // walk the progression downwards, start, start - step, ...
+    pub fn reverse(mut self) -> Self {
+        for (step, prime) in self.steps.iter_mut().zip(self.primes.iter()) {
+            *step = (prime - *step) % prime;
+        }
+        self
+    }
+
+    pub fn advance(&mut self) {
+        let iter = self.residues.iter_mut().zip(self.steps.iter());
+        for ((residue, step), prime) in iter.zip(self.primes.iter()) {
+            *residue += step;
+            if *residue >= *prime { *residue -= prime; }
+        }
+    }
+
+    // true if the current candidate is divisible by one of the primes
+    pub fn has_factor(&self) -> bool {
+        self.residues.contains(&0)
+    }
+
+    // true if forbidden(prime, residue) holds for one of the primes
+    pub fn rejects<F: Fn(u64, u64) -> bool>(&self, forbidden: F) -> bool {
+        let mut iter = self.primes.iter().zip(self.residues.iter());
+        iter.any(|(prime, residue)| forbidden(*prime, *residue))
+    }
+
+}
+
+
+
+// small odd primes used for sieving candidates of the given size,
+// all of them smaller than the candidates themselves
+pub fn sieving_primes(bits: usize) -> Vec<u64> {
+    const P: usize = 5000;
+    static PRIMES: OnceLock<[u64; P]> = OnceLock::new();
+
+    let primes = PRIMES.get_or_init(utils::generate_small_primes::<P>);
+    primes.iter()
+        .skip(1)
+        .filter(|p| bits > 64 || **p < (1 << (bits - 2)))
+        .copied()
+        .collect()
+}
+
+
+
+// primes p >= start in increasing order, found with a segmented sieve of
+// eratosthenes so large bounds only need memory for the square root
+pub struct PrimeIter {
+    low: u64,
+    index: usize,
+    composite: Vec<bool>,
+    base: Vec<u64>,
+}
+
+
+impl PrimeIter {
+
+    const SEGMENT: u64 = 1 << 15;
+
+    pub fn new(start: u64) -> Self {
+        let mut iter = Self { low: start, index: 0, composite: vec![], base: vec![] };
+        iter.fill();
+        iter
+    }
+
+    // sieve [low, low + SEGMENT)
+    fn fill(&mut self) {
+        let high = self.low + Self::SEGMENT;
+        let root = utils::isqrt_u64(high) + 1;
+        if self.base.last().is_none_or(|p| *p < root) {
+            self.base = simple_sieve(2 * root);
+        }
+
+        self.composite.clear();
+        self.composite.resize(Self::SEGMENT as usize, false);
+        for i in self.low..2.min(high) {
+            self.composite[(i - self.low) as usize] = true;
+        }
+        for p in self.base.iter().take_while(|p| *p * *p < high) {
+            let first = (p * p).max(self.low.div_ceil(*p) * p);
+            for multiple in (first..high).step_by(*p as usize) {
+                self.composite[(multiple - self.low) as usize] = true;
+            }
+        }
+        self.index = 0;
+    }
+
+}
+
+
+impl Iterator for PrimeIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            while self.index < self.composite.len() {
+                let i = self.index;
+                self.index += 1;
+                if !self.composite[i] {
+                    return Some(self.low + i as u64);
+                }
+            }
+            self.low += Self::SEGMENT;
+            self.fill();
+        }
+    }
+}
+
+
+
+// all primes below limit
+fn simple_sieve(limit: u64) -> Vec<u64> {
+    let mut composite = vec![false; limit as usize];
+    let mut primes = Vec::new();
+    for n in 2..limit {
+        if composite[n as usize] { continue }
+        primes.push(n);
+        for multiple in (n * n..limit).step_by(n as usize) {
+            composite[multiple as usize] = true;
+        }
+    }
+    primes
+}
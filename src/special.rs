@@ -0,0 +1,143 @@
+use crate::bigint::{BigInt, N, limbs_shr, mul_limbs, sqr_limbs};
+
+
+// reduction modulo k * 2^n + 1 and k * 2^n - 1. a number h * 2^n + l with
+// h = k * h1 + h0 is congruent to h0 * 2^n + l - h1 when k * 2^n = -1 and
+// to h0 * 2^n + l + h1 when k * 2^n = 1, which only needs shifts and a
+// division by k
+#[derive(Clone, Copy)]
+pub struct SpecialCtx {
+    k: u64,
+    n: usize,
+    plus: bool,
+    limbs: usize,
+    pub modulus: BigInt,
+}
+
+
+impl SpecialCtx {
+
+    fn new(k: u64, n: usize, plus: bool) -> Self {
+        if k == 0 {
+            panic!("Multiplier must be non-zero");
+        }
+        // the high half of a product has to fit in N chunks
+        if n + 128 > 64 * N {
+            panic!("Exponent must be at most {}", 64 * N - 128);
+        }
+        let shifted = BigInt::from(k as u128) << n;
+        let modulus = if plus {
+            shifted + BigInt::from(1)
+        } else if shifted > BigInt::from(2) {
+            shifted - BigInt::from(1)
+        } else {
+            panic!("Modulus must be greater than 1");
+        };
+        Self { k, n, plus, limbs: modulus.size() + 1, modulus }
+    }
+
+    // k * 2^n + 1
+    pub fn proth(k: u64, n: usize) -> Self {
+        Self::new(k, n, true)
+    }
+
+    // k * 2^n - 1
+    pub fn riesel(k: u64, n: usize) -> Self {
+        Self::new(k, n, false)
+    }
+
+    // x < modulus^2 given as 2 * N chunks
+    fn reduce_wide(&self, wide: &[u64]) -> BigInt {
+        let n = self.n;
+        let mut low = limbs_shr(wide, 0);
+        low -= (low >> n) << n;
+        let (h1, h0) = limbs_shr(wide, n).div_rem_u64(self.k);
+
+        // h1 is at most about the modulus, so a few corrections are enough
+        let mut result = (BigInt::from(h0 as u128) << n) + low;
+        if self.plus {
+            while result < h1 {
+                result += self.modulus;
+            }
+            result -= h1;
+        } else {
+            result += h1;
+        }
+        while result >= self.modulus {
+            result -= self.modulus;
+        }
+        result
+    }
+
+    pub fn mul_mod(&self, a: BigInt, b: BigInt) -> BigInt {
+        let mut wide = [0; 2 * N];
+        mul_limbs(&a.chunks[..self.limbs], &b.chunks[..self.limbs], &mut wide);
+        self.reduce_wide(&wide)
+    }
+
+    pub fn square_mod(&self, a: BigInt) -> BigInt {
+        let mut wide = [0; 2 * N];
+        sqr_limbs(&a.chunks[..self.limbs], &mut wide);
+        self.reduce_wide(&wide)
+    }
+
+    pub fn pow_mod(&self, base: BigInt, exponent: BigInt) -> BigInt {
+        let mut result = BigInt::from(1);
+        for i in (0..exponent.bits()).rev() {
+            result = self.square_mod(result);
+            if exponent.bit(i) {
+                result = self.mul_mod(result, base);
+            }
+        }
+        result
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::rng;
+    use super::*;
+
+    fn check_reduction(ctx: SpecialCtx) {
+        let m = ctx.modulus;
+        let one = BigInt::from(1);
+        let mut values = vec![BigInt::zero(), one, m - one, m >> 1];
+        for _ in 0..20 {
+            values.push(BigInt::random() % m);
+        }
+        for a in values.iter() {
+            assert_eq!(ctx.square_mod(*a), a.square() % m);
+            for b in values.iter().take(8) {
+                assert_eq!(ctx.mul_mod(*a, *b), (*a * *b) % m);
+            }
+        }
+        let base = values[5];
+        let mut expected = one;
+        for e in 0..40 {
+            assert_eq!(ctx.pow_mod(base, BigInt::from(e)), expected);
+            expected = (expected * base) % m;
+        }
+    }
+
+    #[test]
+    fn reduction_matches_division() {
+        for (k, n) in [(1, 2), (1, 64), (3, 5), (3, 189), (5, 127), (u64::MAX, 1), (u64::MAX, 900)] {
+            check_reduction(SpecialCtx::proth(k, n));
+            check_reduction(SpecialCtx::riesel(k, n));
+        }
+        for _ in 0..20 {
+            let k = rng::u64() >> (rng::u64() % 64);
+            let n = 1 + rng::u64() as usize % 900;
+            check_reduction(SpecialCtx::proth(k.max(1), n));
+            check_reduction(SpecialCtx::riesel(k.max(2), n));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 1920")]
+    fn rejects_large_exponents() {
+        SpecialCtx::proth(1, 1921);
+    }
+}
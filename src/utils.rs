@@ -1,6 +1,8 @@
 
 use crate::PrimeResult;
 use crate::algos::trial_division;
+use crate::bigint::{BigInt, bigint_div};
+use crate::barrett::BarrettCtx;
 
 
 pub fn generate_small_primes<const N: usize>() -> [u64; N] {
@@ -31,13 +33,789 @@ pub fn mod_exp(mut base: u128, mut exponent: u128, modulus: u128) -> u128 {
     if modulus == 1 { return 0 }
 
     let mut result = 1;
-    base = base % modulus;
+    base %= modulus;
     while exponent > 0 {
         if exponent % 2 == 1 {
-            result = (result * base) % modulus;
+            result = mul_mod_u128(result, base, modulus);
         }
-        exponent = exponent >> 1;
-        base = (base * base) % modulus;
+        exponent >>= 1;
+        base = mul_mod_u128(base, base, modulus);
     }
     result
 }
+
+
+
+// a * b mod m for a, b < m without overflowing, the product is only formed
+// directly when it fits in 128 bits
+pub fn mul_mod_u128(mut a: u128, mut b: u128, modulus: u128) -> u128 {
+    if (a | b) >> 64 == 0 {
+        return (a * b) % modulus;
+    }
+
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = if result >= modulus - a { result - (modulus - a) } else { result + a };
+        }
+        a = if a >= modulus - a { a - (modulus - a) } else { a + a };
+        b >>= 1;
+    }
+    result
+}
+
+
+
+// one-off exponentiation, this sets up a barrett context on every call so
+// code that reuses the modulus should keep a BarrettCtx instead
+pub fn pow_mod(base: BigInt, exponent: BigInt, modulus: BigInt) -> BigInt {
+    BarrettCtx::new(modulus).pow_mod(base, exponent)
+}
+
+
+
+pub fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    if a == 0 { return b }
+    if b == 0 { return a }
+
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b { std::mem::swap(&mut a, &mut b); }
+        b -= a;
+        if b == 0 { break; }
+    }
+    a << shift
+}
+
+
+
+pub fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    if a == 0 { return b }
+    if b == 0 { return a }
+
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b { std::mem::swap(&mut a, &mut b); }
+        b -= a;
+        if b == 0 { break; }
+    }
+    a << shift
+}
+
+
+
+pub fn lcm_u128(a: u128, b: u128) -> u128 {
+    if a == 0 || b == 0 { return 0 }
+    (a / gcd_u128(a, b)) * b
+}
+
+
+
+// returns (g, x, y) such that a*x - b*y = g, with 0 < x <= b/g
+// the coefficients of euclid's algorithm alternate in sign, so only
+// their magnitudes are tracked. a = 0 has no such x, as g = b would need
+// y = -1
+pub fn extended_gcd_u128(a: u128, b: u128) -> (u128, u128, u128) {
+    if a == 0 {
+        panic!("Extended gcd needs a > 0");
+    }
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+    let mut odd_step = false;
+    let mut q;
+
+    while r != 0 {
+        q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s + q * s);
+        (old_t, t) = (t, old_t + q * t);
+        odd_step = !odd_step;
+    }
+
+    if odd_step {
+        (old_r, (b / old_r) - old_s, (a / old_r) - old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+
+
+pub fn mod_inverse_u128(a: u128, m: u128) -> Option<u128> {
+    if m == 1 { return Some(0) }
+    let a = a % m;
+    if a == 0 { return None }
+
+    let (g, x, _) = extended_gcd_u128(a, m);
+    if g != 1 { return None }
+    Some(x % m)
+}
+
+
+
+pub fn binary_gcd(mut a: BigInt, mut b: BigInt) -> BigInt {
+    if a.is_zero() { return b }
+    if b.is_zero() { return a }
+
+    let shift = a.trailing_zeros().min(b.trailing_zeros());
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b { std::mem::swap(&mut a, &mut b); }
+        b -= a;
+        if b.is_zero() { break; }
+    }
+    a << shift
+}
+
+
+
+// a*x + b*y where x and y have opposite signs and the result is positive
+fn linear_combination(a: BigInt, x: i128, b: BigInt, y: i128) -> BigInt {
+    if y <= 0 {
+        BigInt::from(x as u128) * a - BigInt::from(y.unsigned_abs()) * b
+    } else {
+        BigInt::from(y as u128) * b - BigInt::from(x.unsigned_abs()) * a
+    }
+}
+
+
+
+pub fn lehmer_gcd(mut a: BigInt, mut b: BigInt) -> BigInt {
+    if a < b { std::mem::swap(&mut a, &mut b); }
+
+    let (mut x, mut y);
+    let (mut c_a, mut c_b, mut c_c, mut c_d): (i128, i128, i128, i128);
+    let mut q;
+
+    while !b.is_zero() {
+        let n = a.size();
+        if n == 0 {
+            return BigInt::from(gcd_u64(a.chunks[0], b.chunks[0]) as u128);
+        }
+
+        // leading 64 bits of a, and the bits of b in the same position
+        let shift = a.chunks[n].leading_zeros();
+        x = (a >> (n * 64 - shift as usize)).chunks[0] as i128;
+        y = (b >> (n * 64 - shift as usize)).chunks[0] as i128;
+
+        (c_a, c_b, c_c, c_d) = (1, 0, 0, 1);
+        loop {
+            if y + c_c == 0 || y + c_d == 0 { break; }
+            q = (x + c_a) / (y + c_c);
+            if q != (x + c_b) / (y + c_d) { break; }
+
+            (c_a, c_c) = (c_c, c_a - q * c_c);
+            (c_b, c_d) = (c_d, c_b - q * c_d);
+            (x, y) = (y, x - q * y);
+        }
+
+        if c_b == 0 {
+            (a, b) = (b, a % b);
+        } else {
+            (a, b) = (
+                linear_combination(a, c_a, b, c_b),
+                linear_combination(a, c_c, b, c_d),
+            );
+        }
+    }
+    a
+}
+
+
+
+pub fn gcd(a: BigInt, b: BigInt) -> BigInt {
+    lehmer_gcd(a, b)
+}
+
+
+
+pub fn lcm(a: BigInt, b: BigInt) -> BigInt {
+    if a.is_zero() || b.is_zero() { return BigInt::zero() }
+    (a / gcd(a, b)) * b
+}
+
+
+
+// returns (g, x, y) such that a*x - b*y = g, with 0 < x <= b/g. unlike
+// the usual a*x + b*y = g the sign of y is folded into the formula, so
+// both coefficients fit in an unsigned BigInt. this leaves no solution
+// for a = 0, which is rejected like in extended_gcd_u128
+pub fn extended_gcd(a: BigInt, b: BigInt) -> (BigInt, BigInt, BigInt) {
+    if a.is_zero() {
+        panic!("Extended gcd needs a > 0");
+    }
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (BigInt::from(1), BigInt::zero());
+    let (mut old_t, mut t) = (BigInt::zero(), BigInt::from(1));
+    let mut odd_step = false;
+    let mut q;
+    let mut rem;
+
+    while !r.is_zero() {
+        (q, rem) = bigint_div(old_r, r);
+        (old_r, r) = (r, rem);
+        (old_s, s) = (s, old_s + q * s);
+        (old_t, t) = (t, old_t + q * t);
+        odd_step = !odd_step;
+    }
+
+    if odd_step {
+        (old_r, (b / old_r) - old_s, (a / old_r) - old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+
+
+pub fn mod_inverse(a: BigInt, m: BigInt) -> Option<BigInt> {
+    let one = BigInt::from(1);
+    if m == one { return Some(BigInt::zero()) }
+    let a = a % m;
+    if a.is_zero() { return None }
+
+    let (g, x, _) = extended_gcd(a, m);
+    if g != one { return None }
+    Some(x % m)
+}
+
+
+
+// assumption for all jacobi variants: n is odd and n > 0
+pub fn jacobi_u64(a: u64, n: u64) -> i8 {
+    jacobi_u128(a as u128, n as u128)
+}
+
+
+
+pub fn jacobi_u128(mut a: u128, mut n: u128) -> i8 {
+    let mut result = 1;
+    let mut zeros;
+    a %= n;
+
+    while a != 0 {
+        zeros = a.trailing_zeros();
+        a >>= zeros;
+        if zeros % 2 == 1 && (n % 8 == 3 || n % 8 == 5) {
+            result = -result;
+        }
+
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+
+    if n == 1 { result } else { 0 }
+}
+
+
+
+pub fn jacobi(mut a: BigInt, mut n: BigInt) -> i8 {
+    let mut result = 1;
+    let mut zeros;
+    a %= n;
+
+    while !a.is_zero() {
+        zeros = a.trailing_zeros();
+        a >>= zeros;
+        if zeros % 2 == 1 && (n.chunks[0] % 8 == 3 || n.chunks[0] % 8 == 5) {
+            result = -result;
+        }
+
+        std::mem::swap(&mut a, &mut n);
+        if a.chunks[0] % 4 == 3 && n.chunks[0] % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+
+    if n == BigInt::from(1) { result } else { 0 }
+}
+
+
+
+pub fn legendre(a: BigInt, p: BigInt) -> i8 {
+    // assumption: p is an odd prime
+    jacobi(a, p)
+}
+
+
+
+// tonelli-shanks, None if a is not a square modulo the odd prime p. a
+// composite p may also give None, a perfect square p always does since
+// no z below has jacobi(z, p) = -1
+pub fn sqrt_mod(a: BigInt, p: BigInt) -> Option<BigInt> {
+    let one = BigInt::from(1);
+    let a = a % p;
+    if a.is_zero() { return Some(a) }
+    if jacobi(a, p) != 1 || isqrt(p).square() == p { return None }
+
+    let ctx = BarrettCtx::new(p);
+    let p_minus_1 = p - one;
+    let s = p_minus_1.trailing_zeros();
+    let q = p_minus_1 >> s;
+
+    let mut z = BigInt::from(2);
+    while jacobi(z, p) != -1 {
+        z = z.increase();
+    }
+
+    let mut m = s;
+    let mut c = ctx.pow_mod(z, q);
+    let mut t = ctx.pow_mod(a, q);
+    let mut r = ctx.pow_mod(a, (q + one) >> 1);
+
+    while t != one {
+        // smallest i with t^(2^i) = 1, only a composite p can run out of i
+        let mut i = 0;
+        let mut t_power = t;
+        while t_power != one {
+            t_power = ctx.square_mod(t_power);
+            i += 1;
+            if i == m { return None }
+        }
+
+        let mut b = c;
+        for _ in 0..(m - i - 1) {
+            b = ctx.square_mod(b);
+        }
+        m = i;
+        c = ctx.square_mod(b);
+        t = ctx.mul_mod(t, c);
+        r = ctx.mul_mod(r, b);
+    }
+    Some(r)
+}
+
+
+
+pub fn kronecker(a: i128, n: i128) -> i8 {
+    if n == 0 {
+        return if a == 1 || a == -1 { 1 } else { 0 };
+    }
+
+    let mut result = 1;
+    if n < 0 && a < 0 {
+        result = -result;
+    }
+
+    let mut n = n.unsigned_abs();
+    let zeros = n.trailing_zeros();
+    if zeros > 0 {
+        if a % 2 == 0 { return 0 }
+        n >>= zeros;
+        if zeros % 2 == 1 && (a & 7 == 3 || a & 7 == 5) {
+            result = -result;
+        }
+    }
+
+    let a = if a >= 0 {
+        a as u128 % n
+    } else {
+        (n - (a.unsigned_abs() % n)) % n
+    };
+
+    result * jacobi_u128(a, n)
+}
+
+
+
+pub fn isqrt_u64(n: u64) -> u64 {
+    isqrt_u128(n as u128) as u64
+}
+
+
+
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 { return n }
+
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
+    let mut y;
+    loop {
+        y = (x + n / x) >> 1;
+        if y >= x { return x }
+        x = y;
+    }
+}
+
+
+
+pub fn isqrt(n: BigInt) -> BigInt {
+    if n.bits() < 2 { return n }
+
+    let mut x = BigInt::from(1) << n.bits().div_ceil(2);
+    let mut y;
+    loop {
+        y = (x + n / x) >> 1;
+        if y >= x { return x }
+        x = y;
+    }
+}
+
+
+
+pub fn iroot_u128(n: u128, k: u32) -> u128 {
+    if k == 1 || n < 2 { return n }
+
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(k);
+    let mut y;
+    loop {
+        let quotient = match x.checked_pow(k - 1) {
+            Some(power) => n / power,
+            None => 0,
+        };
+        y = ((k - 1) as u128 * x + quotient) / k as u128;
+        if y >= x { return x }
+        x = y;
+    }
+}
+
+
+
+pub fn pow(base: BigInt, mut exponent: u32) -> BigInt {
+    let mut result = BigInt::from(1);
+    let mut base = base;
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result *= base;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base.square();
+        }
+    }
+    result
+}
+
+
+
+pub fn iroot(n: BigInt, k: u32) -> BigInt {
+    if k == 1 || n.bits() < 2 { return n }
+
+    let k_big = BigInt::from(k as u128);
+    let k_minus_1 = BigInt::from((k - 1) as u128);
+    let mut x = BigInt::from(1) << n.bits().div_ceil(k as usize);
+    let mut y;
+    let mut quotient;
+    loop {
+        // skip the power when it is certainly larger than n
+        quotient = if (x.bits() - 1) * (k as usize - 1) >= n.bits() {
+            BigInt::zero()
+        } else {
+            n / pow(x, k - 1)
+        };
+        y = (k_minus_1 * x + quotient) / k_big;
+        if y >= x { return x }
+        x = y;
+    }
+}
+
+
+
+// bit i is set if i is a square mod 64
+const SQUARES_MOD_64: u64 = 0x0202021202030213;
+
+
+pub fn is_perfect_square_u128(n: u128) -> bool {
+    if SQUARES_MOD_64 & (1 << (n % 64)) == 0 { return false }
+    let root = isqrt_u128(n);
+    root * root == n
+}
+
+
+
+pub fn is_perfect_square(n: BigInt) -> bool {
+    if SQUARES_MOD_64 & (1 << (n.chunks[0] % 64)) == 0 { return false }
+    let root = isqrt(n);
+    root * root == n
+}
+
+
+
+fn is_small_prime(k: u32) -> bool {
+    k >= 2 && (2..k).take_while(|d| d * d <= k).all(|d| !k.is_multiple_of(d))
+}
+
+
+
+// the k-th powers modulo a prime q = m*k + 1 are 0 and a subgroup of
+// index k, x^m = 1 for the nonzero ones. a few such q rule out most
+// exponents before any root is computed. assumption: k is prime
+fn may_be_power<F: Fn(u64) -> u64>(residue: F, k: u32) -> bool {
+    let mut checked = 0;
+    for m in (2..).step_by(2) {
+        let q = m * k as u64 + 1;
+        if !is_small_prime(q as u32) { continue }
+        let r = residue(q);
+        if r != 0 && mod_exp(r as u128, m as u128, q as u128) != 1 { return false }
+        checked += 1;
+        if checked == 4 { return true }
+    }
+    unreachable!()
+}
+
+
+
+pub fn is_perfect_power_u128(n: u128) -> bool {
+    if n < 4 { return false }
+    if is_perfect_square_u128(n) { return true }
+
+    let bits = 128 - n.leading_zeros();
+    for k in (3..bits).filter(|k| is_small_prime(*k)) {
+        if !may_be_power(|q| (n % q as u128) as u64, k) { continue }
+        let root = iroot_u128(n, k);
+        if root.checked_pow(k) == Some(n) { return true }
+    }
+    false
+}
+
+
+
+pub fn is_perfect_power(n: BigInt) -> bool {
+    if n.bits() < 3 { return false }
+    if is_perfect_square(n) { return true }
+
+    let bits = n.bits() as u32;
+    for k in (3..bits).filter(|k| is_small_prime(*k)) {
+        if !may_be_power(|q| n.rem_u64(q), k) { continue }
+        let root = iroot(n, k);
+        if pow(root, k) == n { return true }
+    }
+    false
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::rng;
+    use super::*;
+
+    // random pairs, sometimes with a large common factor
+    fn pairs() -> Vec<(u128, u128)> {
+        let mut pairs = vec![(1, 1), (1, 0), (0, 7), (12, 18), (u128::MAX, u128::MAX - 1)];
+        for _ in 0..200 {
+            pairs.push((rng::u128(), rng::u128()));
+            let g = rng::u64() as u128 >> 32;
+            pairs.push(((rng::u64() >> 32) as u128 * g, (rng::u64() >> 32) as u128 * g));
+        }
+        pairs
+    }
+
+    #[test]
+    fn gcd_matches_u128() {
+        for (a, b) in pairs() {
+            let expected = BigInt::from(gcd_u128(a, b));
+            assert!(gcd(BigInt::from(a), BigInt::from(b)) == expected);
+            assert!(lehmer_gcd(BigInt::from(a), BigInt::from(b)) == expected);
+            assert!(binary_gcd(BigInt::from(a), BigInt::from(b)) == expected);
+        }
+    }
+
+    #[test]
+    fn gcd_large() {
+        for _ in 0..50 {
+            let g = BigInt::random() >> 724;
+            let a = (BigInt::random() >> 624) * g;
+            let b = (BigInt::random() >> 674) * g;
+            let result = lehmer_gcd(a, b);
+            assert!(result == binary_gcd(a, b));
+            assert!((a % result).is_zero() && (b % result).is_zero());
+            assert!((result % g).is_zero());
+        }
+    }
+
+    #[test]
+    fn extended_gcd_matches_u128() {
+        for (a, b) in pairs().into_iter().filter(|(a, _)| *a > 0) {
+            let (g, x, y) = extended_gcd_u128(a, b);
+            let expected = (BigInt::from(g), BigInt::from(x), BigInt::from(y));
+            assert!(extended_gcd(BigInt::from(a), BigInt::from(b)) == expected);
+            let (a, b) = (BigInt::from(a), BigInt::from(b));
+            assert!(a * expected.1 - b * expected.2 == expected.0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a > 0")]
+    fn extended_gcd_rejects_zero() {
+        extended_gcd(BigInt::zero(), BigInt::from(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "a > 0")]
+    fn extended_gcd_u128_rejects_zero() {
+        extended_gcd_u128(0, 5);
+    }
+
+    #[test]
+    fn mod_inverse_matches_u128() {
+        for (a, m) in pairs().into_iter().filter(|(_, m)| *m > 0) {
+            let expected = mod_inverse_u128(a, m).map(BigInt::from);
+            assert!(mod_inverse(BigInt::from(a), BigInt::from(m)) == expected);
+        }
+        assert!(mod_inverse(BigInt::from(3), BigInt::from(7)) == Some(BigInt::from(5)));
+        assert!(mod_inverse(BigInt::from(6), BigInt::from(9)).is_none());
+    }
+
+    // product of a^((p-1)/2) mod p over the prime factors p of odd n
+    fn euler_criterion(a: u64, mut n: u64) -> i8 {
+        let mut result = 1;
+        let mut p = 3;
+        while n > 1 {
+            while n.is_multiple_of(p) {
+                result *= match mod_exp(a as u128, (p as u128 - 1) / 2, p as u128) {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                };
+                n /= p;
+            }
+            p += 2;
+        }
+        result
+    }
+
+    #[test]
+    fn jacobi_exhaustive() {
+        for n in (1..2000).step_by(2) {
+            for a in 0..2 * n {
+                let expected = euler_criterion(a, n);
+                assert_eq!(jacobi_u64(a, n), expected, "({} / {})", a, n);
+                assert_eq!(kronecker(a as i128, n as i128), expected, "({} / {})", a, n);
+            }
+        }
+    }
+
+    #[test]
+    fn jacobi_bigint_exhaustive() {
+        for n in (1..300).step_by(2) {
+            for a in 0..n {
+                let expected = euler_criterion(a, n);
+                let result = jacobi(BigInt::from(a as u128), BigInt::from(n as u128));
+                assert_eq!(result, expected, "({} / {})", a, n);
+            }
+        }
+        let p = BigInt::from(1000003);
+        for a in 0..1000 {
+            assert_eq!(legendre(BigInt::from(a), p), euler_criterion(a as u64, 1000003));
+        }
+    }
+
+    #[test]
+    fn kronecker_signs_and_twos() {
+        // (a / 2) depends on a mod 8 and (a / -1) on the sign of a
+        let two = |a: i128| if a % 2 == 0 { 0 } else if matches!(a.rem_euclid(8), 1 | 7) { 1 } else { -1 };
+        for n in -200i128..200 {
+            for a in -200i128..200 {
+                let mut expected = if n < 0 && a < 0 { -1 } else { 1 };
+                let mut odd = n.unsigned_abs();
+                if n == 0 {
+                    expected = if a.abs() == 1 { 1 } else { 0 };
+                    odd = 1;
+                }
+                while odd > 0 && odd.is_multiple_of(2) {
+                    expected *= two(a);
+                    odd /= 2;
+                }
+                expected *= euler_criterion(a.rem_euclid(odd as i128) as u64, odd as u64);
+                assert_eq!(kronecker(a, n), expected, "({} / {})", a, n);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_mod_roots() {
+        for p in (3..300u64).step_by(2).filter(|p| crate::small_is_prime(*p)) {
+            let big_p = BigInt::from(p as u128);
+            for a in 0..p {
+                let root = sqrt_mod(BigInt::from(a as u128), big_p);
+                match euler_criterion(a, p) {
+                    -1 => assert!(root.is_none(), "{} mod {}", a, p),
+                    _ => assert_eq!(root.unwrap().square() % big_p, BigInt::from(a as u128)),
+                }
+            }
+        }
+
+        // 2^127 - 1 = 3 mod 4 and 2^64 + 13 = 1 mod 4
+        let one = BigInt::from(1);
+        for p in [(one << 127) - one, (one << 64) + BigInt::from(13)] {
+            for _ in 0..50 {
+                let a = (BigInt::random() % p).square() % p;
+                assert_eq!(sqrt_mod(a, p).unwrap().square() % p, a);
+            }
+        }
+
+        // no z with jacobi(z, p) = -1 exists for a perfect square p
+        for p in [9u128, 25, 49 * 49, 1000003 * 1000003] {
+            assert!(sqrt_mod(BigInt::from(4), BigInt::from(p)).is_none());
+        }
+    }
+
+    #[test]
+    fn roots_bracket_n() {
+        for _ in 0..200 {
+            let n = rng::u128() >> (rng::u64() % 128);
+            let root = isqrt_u128(n);
+            assert!(root * root <= n && (root + 1).checked_mul(root + 1).is_none_or(|sq| sq > n));
+            for k in [3, 5, 7, 40] {
+                let root = iroot_u128(n, k);
+                assert!(root.pow(k) <= n && (root + 1).checked_pow(k).is_none_or(|p| p > n));
+            }
+
+            let n = BigInt::random() >> (1023 - rng::u64() as usize % 900);
+            let root = isqrt(n);
+            let next = root + BigInt::from(1);
+            assert!(root * root <= n && next * next > n);
+            for k in [3, 5, 7, 40] {
+                let root = iroot(n, k);
+                assert!(pow(root, k) <= n && pow(root + BigInt::from(1), k) > n);
+            }
+        }
+    }
+
+    #[test]
+    fn perfect_powers_exhaustive() {
+        let limit = 1 << 16;
+        let mut powers = vec![false; limit];
+        for a in 2..256 {
+            let mut power = a * a;
+            while power < limit {
+                powers[power] = true;
+                power *= a;
+            }
+        }
+        for (n, expected) in powers.iter().enumerate() {
+            assert_eq!(is_perfect_power_u128(n as u128), *expected, "{}", n);
+            if n < 5000 {
+                assert_eq!(is_perfect_power(BigInt::from(n as u128)), *expected, "{}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn perfect_powers_large() {
+        for k in [2, 3, 5, 7, 11, 13, 31] {
+            let base = 1000 + rng::u64() % 1000;
+            let n = (base as u128).pow(k.min(11));
+            assert!(is_perfect_power_u128(n));
+            assert!(!is_perfect_power_u128(n + 1));
+
+            let root = (BigInt::random() >> 994) + BigInt::from(3);
+            let n = pow(root, k);
+            assert!(is_perfect_power(n));
+            assert!(!is_perfect_power(n + BigInt::from(1)));
+            assert!(is_perfect_square(pow(root, 2)));
+            assert!(iroot(n, k) == root);
+        }
+    }
+}